@@ -50,6 +50,61 @@ fn if_else_multiple_conditions() {
     println!("Outside it is {desc}");
 }
 
+/// The descriptions above as an enum rather than string literals,
+/// so the compiler can check we handle every kind of weather.
+#[derive(Debug, PartialEq)]
+pub enum Weather {
+    Hot,
+    Pleasant,
+    Chilly,
+    Cold,
+}
+
+/// Classifies a temperature with the same thresholds as above.
+///
+/// Comparisons are strict: a threshold value belongs to the colder
+/// kind of weather, 30 is pleasant and 31 is hot.
+pub fn classify_temperature(t: i32) -> Weather {
+    if t > 30 {
+        Weather::Hot
+    } else if t > 20 {
+        Weather::Pleasant
+    } else if t > 10 {
+        Weather::Chilly
+    } else {
+        Weather::Cold
+    }
+}
+
+impl std::fmt::Display for Weather {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let desc = match self {
+            Weather::Hot => "hot",
+            Weather::Pleasant => "pleasant",
+            Weather::Chilly => "chilly",
+            Weather::Cold => "cold",
+        };
+        write!(f, "{desc}")
+    }
+}
+
+#[test]
+fn test_classify_temperature() {
+    assert_eq!(classify_temperature(31), Weather::Hot);
+    assert_eq!(classify_temperature(30), Weather::Pleasant);
+    assert_eq!(classify_temperature(21), Weather::Pleasant);
+    assert_eq!(classify_temperature(20), Weather::Chilly);
+    assert_eq!(classify_temperature(11), Weather::Chilly);
+    assert_eq!(classify_temperature(10), Weather::Cold);
+    assert_eq!(classify_temperature(-5), Weather::Cold);
+
+    assert_eq!(classify_temperature(25).to_string(), "pleasant");
+    assert_eq!(
+        format!("Outside it is {}", Weather::Hot),
+        "Outside it is hot"
+    );
+}
+
 /// EXERCISE: Complete the function to return the correct discount
 fn calculate_discount(total: f64, is_member: bool) -> f64 {
     // Implement the following logic:
@@ -58,7 +113,15 @@ fn calculate_discount(total: f64, is_member: bool) -> f64 {
     // - If total >= 50.0: 5% discount
     // - Otherwise: no discount (0.0)
 
-    0.0
+    if total >= 100.0 && is_member {
+        0.20
+    } else if total >= 100.0 {
+        0.10
+    } else if total >= 50.0 {
+        0.05
+    } else {
+        0.0
+    }
 }
 
 #[test]
@@ -67,6 +130,12 @@ fn test_discount() {
     assert_eq!(calculate_discount(120.0, false), 0.10);
     assert_eq!(calculate_discount(60.0, true), 0.05);
     assert_eq!(calculate_discount(30.0, false), 0.0);
+
+    // boundaries are part of the upper tier
+    assert_eq!(calculate_discount(100.0, true), 0.20);
+    assert_eq!(calculate_discount(100.0, false), 0.10);
+    assert_eq!(calculate_discount(50.0, false), 0.05);
+    assert_eq!(calculate_discount(49.99, true), 0.0);
 }
 
 /// This uses Rust's `loop` construct which is ideal when:
@@ -137,6 +206,36 @@ fn const_in_loop() {
     // overview.
 }
 
+/// The error returned when a factorial does not fit in the
+/// result type, it carries the `n` we tried to compute.
+#[derive(Debug, PartialEq)]
+pub enum FactorialError {
+    Overflow(u32),
+}
+
+/// `factorial` above silently overflows (or panics in debug mode)
+/// for any `n` above 12 as the result does not fit in a `u32`.
+///
+/// This version computes in a `u64` and uses `checked_mul`, which
+/// returns `None` instead of overflowing, so we can report an
+/// error when the result does not fit.
+pub fn checked_factorial(n: u32) -> Result<u64, FactorialError> {
+    let mut result: u64 = 1;
+    for i in 1..=n as u64 {
+        result = result.checked_mul(i).ok_or(FactorialError::Overflow(n))?;
+    }
+    Ok(result)
+}
+
+#[test]
+fn test_checked_factorial() {
+    assert_eq!(checked_factorial(0), Ok(1));
+    assert_eq!(checked_factorial(5), Ok(FACT_5 as u64));
+    assert_eq!(checked_factorial(20), Ok(2_432_902_008_176_640_000));
+    assert_eq!(checked_factorial(21), Err(FactorialError::Overflow(21)));
+    assert_eq!(checked_factorial(100), Err(FactorialError::Overflow(100)));
+}
+
 /// This uses Rust's `while` construct which is ideal when:
 /// - You have a clear condition checked BEFORE each iteration
 /// - The loop might not need to run at all (condition could be false initially)
@@ -158,6 +257,38 @@ fn iterate_with_while() {
     println!("Blastoff!")
 }
 
+/// The countdown above returned as a `Vec` so we can check it:
+/// `countdown(3)` is `[3, 2, 1]`, there is nothing to count
+/// down from 0 so `countdown(0)` is empty.
+pub fn countdown(n: u32) -> Vec<u32> {
+    let mut count = n;
+    let mut numbers = Vec::new();
+
+    while count > 0 {
+        numbers.push(count);
+        count -= 1;
+    }
+
+    numbers
+}
+
+/// Same as `countdown` but as text, ending with "Blastoff!".
+pub fn blastoff(n: u32) -> Vec<String> {
+    let mut lines: Vec<String> = countdown(n).iter().map(|c| c.to_string()).collect();
+    lines.push(String::from("Blastoff!"));
+    lines
+}
+
+#[test]
+fn test_countdown() {
+    assert_eq!(countdown(5), [5, 4, 3, 2, 1]);
+    assert_eq!(countdown(1), [1]);
+    assert!(countdown(0).is_empty());
+
+    assert_eq!(blastoff(3), ["3", "2", "1", "Blastoff!"]);
+    assert_eq!(blastoff(0), ["Blastoff!"]);
+}
+
 /// This uses Rust's `for` construct which is ideal when:
 /// - You want to iterate over a known collection or range
 /// - The number of iterations is known or can be determined upfront
@@ -208,3 +339,114 @@ fn reverse_iterator() {
 
     println!("BOOOOM!");
 }
+
+/// # Writing our own iterator
+///
+/// Anything can be iterated with a `for` loop as long as it implements
+/// the `Iterator` trait, which only requires a `next` method returning
+/// `Some(item)` while there are items and then `None`.
+///
+/// `Evens` is an ADAPTER: it wraps another iterator (here a range)
+/// and only yields its even numbers.
+struct Evens {
+    range: std::ops::Range<i32>,
+}
+
+impl Iterator for Evens {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // we pull numbers out of the range until we find an even
+        // one, `?` returns None once the range is exhausted
+        loop {
+            let n = self.range.next()?;
+            if n % 2 == 0 {
+                return Some(n);
+            }
+        }
+    }
+}
+
+/// Returns an iterator over the even numbers of the range.
+///
+/// NB: `range.filter(|n| n % 2 == 0)` does the same, but
+/// it is good to know what happens behind the scenes.
+pub fn evens(range: std::ops::Range<i32>) -> impl Iterator<Item = i32> {
+    Evens { range }
+}
+
+#[test]
+fn custom_iterator() {
+    assert_eq!(evens(0..10).collect::<Vec<_>>(), [0, 2, 4, 6, 8]);
+    assert_eq!(evens(-3..3).collect::<Vec<_>>(), [-2, 0, 2]);
+    assert_eq!(evens(5..5).count(), 0);
+    assert_eq!(evens(1..2).next(), None);
+
+    // it can be used in a for loop as any other iterator
+    let mut sum = 0;
+    for n in evens(1..7) {
+        sum += n;
+    }
+    assert_eq!(sum, 2 + 4 + 6);
+}
+
+/// An iterator over the Fibonacci numbers: 0, 1, 1, 2, 3, 5, 8...
+///
+/// Unlike `Evens` it doesn't wrap another iterator, it computes
+/// each number from the two previous ones. The numbers grow fast
+/// and the next one would overflow a `u64` after the 94th, so the
+/// iterator stops there instead of panicking (or wrapping in
+/// release mode).
+pub struct Fibonacci {
+    // the next number to yield, None once we are done
+    current: Option<u64>,
+    // the number after it, None if it doesn't fit in a u64
+    next: Option<u64>,
+}
+
+impl Fibonacci {
+    /// Creates an iterator starting at 0.
+    pub fn new() -> Self {
+        Self {
+            current: Some(0),
+            next: Some(1),
+        }
+    }
+}
+
+impl Default for Fibonacci {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for Fibonacci {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+        // checked_add gives None on overflow, which will
+        // end the iterator once we get to that number
+        let after = self.next.and_then(|next| current.checked_add(next));
+        self.current = self.next;
+        self.next = after;
+        Some(current)
+    }
+}
+
+#[test]
+fn fibonacci_iterator() {
+    assert_eq!(
+        Fibonacci::new().take(10).collect::<Vec<_>>(),
+        [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+    );
+
+    // the iterator ends with the largest Fibonacci number fitting in a u64
+    assert_eq!(Fibonacci::new().count(), 94);
+    assert_eq!(Fibonacci::new().last(), Some(12_200_160_415_121_876_738));
+
+    // and keeps returning None once done
+    let mut fib = Fibonacci::new().skip(94);
+    assert_eq!(fib.next(), None);
+    assert_eq!(fib.next(), None);
+}
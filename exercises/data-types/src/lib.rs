@@ -98,6 +98,35 @@ fn addressing_integer_under_overflow() {
     println!("{overflow_u8}");
 }
 
+/// Sums the values, stopping at `u8::MAX` instead of overflowing.
+pub fn sum_saturating(values: &[u8]) -> u8 {
+    values.iter().fold(0u8, |acc, v| acc.saturating_add(*v))
+}
+
+/// Sums the values, going back to 0 after `u8::MAX` (i.e. the sum
+/// modulo 256) instead of overflowing.
+pub fn sum_wrapping(values: &[u8]) -> u8 {
+    values.iter().fold(0u8, |acc, v| acc.wrapping_add(*v))
+}
+
+#[test]
+fn overflow_strategies() {
+    // no overflow, both strategies agree
+    assert_eq!(sum_saturating(&[100, 50]), 150);
+    assert_eq!(sum_wrapping(&[100, 50]), 150);
+
+    // 200 + 100 = 300 does not fit in a u8
+    assert_eq!(sum_saturating(&[200, 100]), 255);
+    // 300 - 256 = 44
+    assert_eq!(sum_wrapping(&[200, 100]), 44);
+
+    // saturation is sticky, wrapping keeps counting
+    assert_eq!(sum_saturating(&[255, 1, 10]), 255);
+    assert_eq!(sum_wrapping(&[255, 1, 10]), 10);
+
+    assert_eq!(sum_saturating(&[]), 0);
+}
+
 /// IEEE 754 floating-point numbers:
 /// | Type   | Size (bits) | Precision      | Notes               |
 /// |--------|-------------|----------------|---------------------|
@@ -190,6 +219,73 @@ fn compound_types_array() {
     // EXERCISE: initialize a u64 array with all values being 42
 }
 
+/// A solution to the exercise above which works for any array
+/// length: `N` is a CONST GENERIC, a generic parameter which is
+/// a value known at compile time instead of a type.
+pub fn init_array<const N: usize>(value: u64) -> [u64; N] {
+    [value; N]
+}
+
+/// Same as `init_array` but each element is computed from its
+/// index by the `f` closure.
+pub fn init_array_with<const N: usize, F: Fn(usize) -> u64>(f: F) -> [u64; N] {
+    std::array::from_fn(f)
+}
+
+#[test]
+fn array_initialization() {
+    // N is inferred from the type of the variable
+    let answers: [u64; 5] = init_array(42);
+    assert_eq!(answers, [42; 5]);
+    assert!(answers.iter().all(|v| *v == 42));
+
+    // or given explicitly
+    let empty = init_array::<0>(42);
+    assert!(empty.is_empty());
+
+    let indexes: [u64; 4] = init_array_with(|i| i as u64);
+    assert_eq!(indexes, [0, 1, 2, 3]);
+
+    let squares: [u64; 4] = init_array_with(|i| (i * i) as u64);
+    assert_eq!(squares, [0, 1, 4, 9]);
+}
+
+/// Returns the element at `index`, or `None` if `index` is out of
+/// the bounds of the slice.
+///
+/// Indexing with `slice[index]` is bounds checked too, but it
+/// panics when out of bounds. `get` returns an `Option` so the
+/// caller has to handle the missing element.
+pub fn safe_get<T>(slice: &[T], index: usize) -> Option<&T> {
+    slice.get(index)
+}
+
+/// Same as `safe_get` but returns a mutable reference, so the
+/// element can be modified in place.
+pub fn safe_get_mut<T>(slice: &mut [T], index: usize) -> Option<&mut T> {
+    slice.get_mut(index)
+}
+
+#[test]
+fn array_bounds_checking() {
+    let mut integers = [1, 2, 3, 4, 5, 6];
+
+    assert_eq!(safe_get(&integers, 0), Some(&1));
+    assert_eq!(safe_get(&integers, 5), Some(&6));
+    // the length is the first index out of bounds
+    assert_eq!(safe_get(&integers, 6), None);
+    assert_eq!(safe_get(&integers, usize::MAX), None);
+
+    if let Some(i) = safe_get_mut(&mut integers, 0) {
+        *i = 42;
+    }
+    assert_eq!(integers[0], 42);
+    assert_eq!(safe_get_mut(&mut integers, 10), None);
+
+    let empty: [u8; 0] = [];
+    assert_eq!(safe_get(&empty, 0), None);
+}
+
 /// # Destructuring introduction in Rust
 ///
 /// Destructuring is the process of **breaking down complex data types** into their individual components.
@@ -120,6 +120,206 @@ fn data_carrying_enums2() {
     println!("rs={rs:?}");
 }
 
+/// The shape we are going to use in the following examples,
+/// it is public so that it can be used outside of the tests.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Shape {
+    Dot,
+    Circle(u32),
+    Rectangle { width: u32, height: u32 },
+    Triangle { base: u32, height: u32 },
+}
+
+// to illustrate pattern matching lets implement
+// a function for the Shape enum
+impl Shape {
+    pub fn area(&self) -> f64 {
+        match self {
+            Shape::Dot => 0.0,
+            // as we have seen earlier with tuples what we do
+            // here is called DESTRUCTURING
+            Shape::Circle(r) => PI * (*r as f64).powi(2),
+            Shape::Rectangle { width, height } => *width as f64 * (*height) as f64,
+            Shape::Triangle { base, height } => 0.5 * *base as f64 * (*height) as f64,
+        }
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        match self {
+            Shape::Dot => 0.0,
+            Shape::Circle(r) => 2.0 * PI * *r as f64,
+            Shape::Rectangle { width, height } => 2.0 * (*width as f64 + *height as f64),
+            // base and height are not enough to know the other two
+            // sides so we consider the triangle to be isosceles
+            Shape::Triangle { base, height } => {
+                let half_base = *base as f64 / 2.0;
+                let side = (half_base.powi(2) + (*height as f64).powi(2)).sqrt();
+                *base as f64 + 2.0 * side
+            }
+        }
+    }
+
+    /// Multiplies every dimension of the shape by `factor`, so its
+    /// area is multiplied by `factor²`.
+    ///
+    /// Returns [`ScaleError::Overflow`] and leaves the shape unchanged
+    /// if a scaled dimension doesn't fit in a `u32`.
+    pub fn scale(&mut self, factor: u32) -> Result<(), ScaleError> {
+        // checked_mul returns None on overflow, ok_or turns
+        // it into an error we can propagate with `?`
+        let mul = |x: u32| x.checked_mul(factor).ok_or(ScaleError::Overflow);
+        match self {
+            Shape::Dot => {}
+            // destructuring a `&mut Shape` gives us `&mut u32`
+            // we can modify in place
+            Shape::Circle(r) => *r = mul(*r)?,
+            // both dimensions are checked before assigning any of
+            // them so that we don't scale only half of the shape
+            Shape::Rectangle { width, height } => {
+                (*width, *height) = (mul(*width)?, mul(*height)?);
+            }
+            Shape::Triangle { base, height } => {
+                (*base, *height) = (mul(*base)?, mul(*height)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the exact area of the shape as an integer, or `None`
+    /// if it is not an integer.
+    ///
+    /// Unlike `area` there is no rounding: the area of a circle is
+    /// irrational and the area of a triangle is only an integer if
+    /// `base * height` is even.
+    pub fn area_u64(&self) -> Option<u64> {
+        match self {
+            Shape::Dot => Some(0),
+            Shape::Circle(_) => None,
+            // the product of two u32 may overflow a u32 but always fits
+            // in a u64, checked_mul makes it explicit that we checked
+            Shape::Rectangle { width, height } => (*width as u64).checked_mul(*height as u64),
+            Shape::Triangle { base, height } => {
+                let double_area = (*base as u64).checked_mul(*height as u64)?;
+                (double_area % 2 == 0).then_some(double_area / 2)
+            }
+        }
+    }
+}
+
+/// The errors we can get when scaling a `Shape`.
+#[derive(Debug, PartialEq)]
+pub enum ScaleError {
+    // a scaled dimension doesn't fit in a u32
+    Overflow,
+}
+
+// Shapes are ordered by area so that a slice of shapes can be
+// sorted with `sort()`.
+//
+// Floats are only PartialOrd because NaN cannot be compared to
+// anything. Our dimensions are integers so an area is never NaN
+// and the order is total. Shapes with the same area are then
+// ordered by variant and dimensions: Ord must agree with Eq so
+// two different shapes are never Equal.
+impl Ord for Shape {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.area()
+            .total_cmp(&other.area())
+            .then_with(|| self.dimensions().cmp(&other.dimensions()))
+    }
+}
+
+impl PartialOrd for Shape {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Debug is meant for developers, Display is what we show to users
+impl std::fmt::Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Shape::Dot => write!(f, "dot"),
+            Shape::Circle(r) => write!(f, "circle(r={r})"),
+            Shape::Rectangle { width, height } => write!(f, "rectangle({width}x{height})"),
+            Shape::Triangle { base, height } => write!(f, "triangle(b={base}, h={height})"),
+        }
+    }
+}
+
+/// The errors we can get when parsing a `Shape` from a string.
+#[derive(Debug, PartialEq)]
+pub enum ShapeParseError {
+    // there is nothing to parse
+    Empty,
+    // the shape name is not one we know about
+    UnknownShape(String),
+    // the shape is known but doesn't have the right number of dimensions
+    WrongDimensions { expected: usize, found: usize },
+    // a dimension is not a valid u32
+    InvalidDimension(std::num::ParseIntError),
+}
+
+// FromStr is the trait behind `str::parse`, implementing it
+// lets us write "circle 5".parse::<Shape>()
+//
+// Supported formats are: "dot", "circle R", "rect W H" and
+// "triangle B H", words being separated by whitespaces.
+impl std::str::FromStr for Shape {
+    type Err = ShapeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let name = words.next().ok_or(ShapeParseError::Empty)?;
+
+        let expected = match name {
+            "dot" => 0,
+            "circle" => 1,
+            "rect" | "triangle" => 2,
+            _ => return Err(ShapeParseError::UnknownShape(String::from(name))),
+        };
+
+        let dims = words
+            .map(|w| w.parse::<u32>())
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(ShapeParseError::InvalidDimension)?;
+        if dims.len() != expected {
+            return Err(ShapeParseError::WrongDimensions {
+                expected,
+                found: dims.len(),
+            });
+        }
+
+        // the number of dimensions has been checked
+        // above so indexing cannot panic
+        Ok(match name {
+            "dot" => Shape::Dot,
+            "circle" => Shape::Circle(dims[0]),
+            "rect" => Shape::Rectangle {
+                width: dims[0],
+                height: dims[1],
+            },
+            _ => Shape::Triangle {
+                base: dims[0],
+                height: dims[1],
+            },
+        })
+    }
+}
+
+impl Shape {
+    // the variant and dimensions of the shape, used to
+    // break ties between shapes of the same area
+    fn dimensions(&self) -> (u8, u32, u32) {
+        match self {
+            Shape::Dot => (0, 0, 0),
+            Shape::Circle(r) => (1, *r, 0),
+            Shape::Rectangle { width, height } => (2, *width, *height),
+            Shape::Triangle { base, height } => (3, *base, *height),
+        }
+    }
+}
+
 /// # Pattern Matching
 ///
 /// **Pattern matching lets you compare values against patterns and execute code based on which pattern matches.**
@@ -139,38 +339,35 @@ fn data_carrying_enums2() {
 /// 3. **Powerful patterns**: Can destructure and bind variables
 #[test]
 fn enum_pattern_matching() {
-    #[derive(Debug)]
-    enum Shape {
-        Dot,
-        Circle(u32),
-        Rectangle { width: u32, height: u32 },
-    }
-
-    // to illustrate pattern matching lets implement
-    // a function for the Shape enum
-    impl Shape {
-        fn area(&self) -> f64 {
-            match self {
-                Shape::Dot => 0.0,
-                // as we have seen earlier with tuples what we do
-                // here is called DESTRUCTURING
-                Shape::Circle(r) => PI * (*r as f64).powi(2),
-                Shape::Rectangle { width, height } => *width as f64 * (*height) as f64,
-            }
-        }
-    }
-
     let dot = Shape::Dot;
     println!("dot area={}", dot.area());
+    assert_eq!(dot.area(), 0.0);
 
     let circle = Shape::Circle(2);
     println!("circle area={}", circle.area());
+    assert!((circle.area() - 4.0 * PI).abs() < f64::EPSILON);
 
     let rect = Shape::Rectangle {
         width: 5,
         height: 3,
     };
     println!("rectangle area={}", rect.area());
+    assert_eq!(rect.area(), 15.0);
+
+    let triangle = Shape::Triangle { base: 5, height: 3 };
+    println!("triangle area={}", triangle.area());
+    assert_eq!(triangle.area(), 7.5);
+
+    println!("dot perimeter={}", dot.perimeter());
+    assert_eq!(dot.perimeter(), 0.0);
+    println!("circle perimeter={}", circle.perimeter());
+    assert!((circle.perimeter() - 4.0 * PI).abs() < 1e-9);
+    println!("rectangle perimeter={}", rect.perimeter());
+    assert_eq!(rect.perimeter(), 16.0);
+    // sides of the isosceles triangle are sqrt(3² + 4²) = 5
+    let triangle = Shape::Triangle { base: 6, height: 4 };
+    println!("triangle perimeter={}", triangle.perimeter());
+    assert!((triangle.perimeter() - 16.0).abs() < 1e-9);
 
     // This demonstrates how enums with pattern matching can achieve
     // polymorphism similar to inheritance in OOP languages, but with key differences:
@@ -188,6 +385,246 @@ fn enum_pattern_matching() {
     // - Data and behavior are more explicitly separated
 }
 
+/// Sums the areas of all the shapes.
+pub fn total_area(shapes: &[Shape]) -> f64 {
+    shapes.iter().map(Shape::area).sum()
+}
+
+/// Returns the shape with the largest area, the last one if several
+/// have the same area, or `None` if there is no shape.
+///
+/// `f64` is not `Ord` (because of NaN) so we cannot use `max_by_key`,
+/// `total_cmp` gives us a total ordering of floats instead.
+pub fn largest_shape(shapes: &[Shape]) -> Option<&Shape> {
+    shapes.iter().max_by(|a, b| a.area().total_cmp(&b.area()))
+}
+
+#[test]
+fn shapes_aggregation() {
+    let shapes = [
+        Shape::Dot,
+        Shape::Circle(1),
+        Shape::Rectangle {
+            width: 5,
+            height: 3,
+        },
+        Shape::Triangle { base: 4, height: 2 },
+    ];
+
+    let expected = PI + 15.0 + 4.0;
+    assert!((total_area(&shapes) - expected).abs() < 1e-9);
+    assert!(matches!(
+        largest_shape(&shapes),
+        Some(Shape::Rectangle {
+            width: 5,
+            height: 3
+        })
+    ));
+
+    assert_eq!(total_area(&[]), 0.0);
+    assert!(largest_shape(&[]).is_none());
+    assert!(matches!(largest_shape(&[Shape::Dot]), Some(Shape::Dot)));
+}
+
+#[test]
+fn shapes_ordering() {
+    let mut shapes = vec![
+        Shape::Rectangle {
+            width: 10,
+            height: 10,
+        },
+        Shape::Circle(2),
+        Shape::Dot,
+        Shape::Triangle { base: 4, height: 3 },
+        Shape::Rectangle {
+            width: 2,
+            height: 3,
+        },
+    ];
+    shapes.sort();
+
+    assert_eq!(
+        shapes,
+        [
+            Shape::Dot,
+            // a triangle of area 6 comes after a rectangle
+            // of area 6 as variants break ties
+            Shape::Rectangle {
+                width: 2,
+                height: 3,
+            },
+            Shape::Triangle { base: 4, height: 3 },
+            Shape::Circle(2),
+            Shape::Rectangle {
+                width: 10,
+                height: 10,
+            },
+        ]
+    );
+
+    assert!(Shape::Circle(1) < Shape::Circle(2));
+    assert_eq!(Shape::Dot.cmp(&Shape::Dot), std::cmp::Ordering::Equal);
+    assert_ne!(
+        Shape::Rectangle {
+            width: 2,
+            height: 3
+        }
+        .cmp(&Shape::Rectangle {
+            width: 3,
+            height: 2
+        }),
+        std::cmp::Ordering::Equal
+    );
+}
+
+#[test]
+fn shapes_scaling() {
+    let mut dot = Shape::Dot;
+    assert_eq!(dot.scale(3), Ok(()));
+    assert_eq!(dot, Shape::Dot);
+
+    let mut circle = Shape::Circle(2);
+    let area = circle.area();
+    assert_eq!(circle.scale(3), Ok(()));
+    assert_eq!(circle, Shape::Circle(6));
+    assert!((circle.area() - 9.0 * area).abs() < 1e-9);
+
+    let mut rect = Shape::Rectangle {
+        width: 5,
+        height: 3,
+    };
+    assert_eq!(rect.scale(2), Ok(()));
+    assert_eq!(
+        rect,
+        Shape::Rectangle {
+            width: 10,
+            height: 6
+        }
+    );
+    assert_eq!(rect.area(), 4.0 * 15.0);
+
+    let mut triangle = Shape::Triangle { base: 5, height: 3 };
+    assert_eq!(triangle.scale(2), Ok(()));
+    assert_eq!(triangle.area(), 4.0 * 7.5);
+}
+
+#[test]
+fn shapes_scaling_overflow() {
+    let mut circle = Shape::Circle(u32::MAX);
+    assert_eq!(circle.scale(2), Err(ScaleError::Overflow));
+    assert_eq!(circle, Shape::Circle(u32::MAX));
+    // scaling by 1 never overflows
+    assert_eq!(circle.scale(1), Ok(()));
+
+    // the width would fit but not the height, the
+    // rectangle is left as it was
+    let mut rect = Shape::Rectangle {
+        width: 1,
+        height: u32::MAX / 2 + 1,
+    };
+    assert_eq!(rect.scale(2), Err(ScaleError::Overflow));
+    assert_eq!(
+        rect,
+        Shape::Rectangle {
+            width: 1,
+            height: u32::MAX / 2 + 1
+        }
+    );
+
+    let mut triangle = Shape::Triangle {
+        base: u32::MAX / 3 + 1,
+        height: 1,
+    };
+    assert_eq!(triangle.scale(3), Err(ScaleError::Overflow));
+    assert_eq!(triangle.scale(2), Ok(()));
+}
+
+#[test]
+fn shapes_display() {
+    assert_eq!(Shape::Dot.to_string(), "dot");
+    assert_eq!(Shape::Circle(5).to_string(), "circle(r=5)");
+    let rect = Shape::Rectangle {
+        width: 3,
+        height: 5,
+    };
+    assert_eq!(format!("{rect}"), "rectangle(3x5)");
+    assert_eq!(
+        Shape::Triangle { base: 4, height: 3 }.to_string(),
+        "triangle(b=4, h=3)"
+    );
+}
+
+#[test]
+fn shapes_from_str() {
+    assert_eq!("dot".parse(), Ok(Shape::Dot));
+    assert_eq!("circle 5".parse(), Ok(Shape::Circle(5)));
+    assert_eq!(
+        "rect 3 5".parse(),
+        Ok(Shape::Rectangle {
+            width: 3,
+            height: 5
+        })
+    );
+    assert_eq!(
+        "  triangle   4 3 ".parse(),
+        Ok(Shape::Triangle { base: 4, height: 3 })
+    );
+
+    assert_eq!("".parse::<Shape>(), Err(ShapeParseError::Empty));
+    assert_eq!(
+        "square x".parse::<Shape>(),
+        Err(ShapeParseError::UnknownShape(String::from("square")))
+    );
+    assert_eq!(
+        "circle".parse::<Shape>(),
+        Err(ShapeParseError::WrongDimensions {
+            expected: 1,
+            found: 0
+        })
+    );
+    assert_eq!(
+        "dot 1".parse::<Shape>(),
+        Err(ShapeParseError::WrongDimensions {
+            expected: 0,
+            found: 1
+        })
+    );
+    assert!(matches!(
+        "rect 3 -5".parse::<Shape>(),
+        Err(ShapeParseError::InvalidDimension(_))
+    ));
+}
+
+#[test]
+fn shapes_exact_area() {
+    assert_eq!(Shape::Dot.area_u64(), Some(0));
+    assert_eq!(Shape::Circle(1).area_u64(), None);
+    let rect = Shape::Rectangle {
+        width: 3,
+        height: 5,
+    };
+    assert_eq!(rect.area_u64(), Some(15));
+
+    // 100_000 * 100_000 overflows a u32 but not a u64
+    let big = Shape::Rectangle {
+        width: 100_000,
+        height: 100_000,
+    };
+    assert_eq!(100_000u32.checked_mul(100_000), None);
+    assert_eq!(big.area_u64(), Some(10_000_000_000));
+    let biggest = Shape::Rectangle {
+        width: u32::MAX,
+        height: u32::MAX,
+    };
+    assert_eq!(biggest.area_u64(), Some(u32::MAX as u64 * u32::MAX as u64));
+
+    // half of an odd number is not an integer
+    let even = Shape::Triangle { base: 4, height: 3 };
+    assert_eq!(even.area_u64(), Some(6));
+    let odd = Shape::Triangle { base: 3, height: 3 };
+    assert_eq!(odd.area_u64(), None);
+}
+
 /// Pattern matching must always handle ALL the cases
 /// otherwise the compiler will complain about it
 #[test]
@@ -357,7 +794,60 @@ fn pattern_matching_beyond_enums_3() {
 /// - make a function named `tell_me` taking such an enum as parameter
 ///     - printing the range as above if it is an int
 ///     - answering to the question as above if it is a string
+///
+/// Here is a possible solution, `tell_me` returns the answer
+/// instead of printing it so that we can test it.
+#[derive(Debug)]
+pub enum About {
+    Int(i32),
+    Text(String),
+}
+
+impl About {
+    pub fn tell_me(&self) -> String {
+        match self {
+            About::Int(i) => match i {
+                0..10 => format!("{i} is in [0; 9["),
+                10..=20 => format!("{i} is in [10; 20]"),
+                42 => format!(
+                    "{i} is The Answer to the Ultimate Question of Life, The Universe, and Everything"
+                ),
+                _ => format!("{i} is not important"),
+            },
+            About::Text(s) => match s.as_str() {
+                "What is the answer to the ultimate question of life, the universe, and everything ?" => {
+                    String::from("42")
+                }
+                "What is the color of the white horse of Henry the 4th?" => String::from("white"),
+                _ => String::from("I don't have an answer"),
+            },
+        }
+    }
+}
+
 #[test]
 fn enum_exercise() {
-    enum About {}
+    assert_eq!(About::Int(0).tell_me(), "0 is in [0; 9[");
+    assert_eq!(About::Int(9).tell_me(), "9 is in [0; 9[");
+    assert_eq!(About::Int(10).tell_me(), "10 is in [10; 20]");
+    assert_eq!(About::Int(20).tell_me(), "20 is in [10; 20]");
+    assert_eq!(
+        About::Int(42).tell_me(),
+        "42 is The Answer to the Ultimate Question of Life, The Universe, and Everything"
+    );
+    assert_eq!(About::Int(1337).tell_me(), "1337 is not important");
+    assert_eq!(About::Int(-1).tell_me(), "-1 is not important");
+
+    let q = About::Text(String::from(
+        "What is the answer to the ultimate question of life, the universe, and everything ?",
+    ));
+    assert_eq!(q.tell_me(), "42");
+
+    let q = About::Text(String::from(
+        "What is the color of the white horse of Henry the 4th?",
+    ));
+    assert_eq!(q.tell_me(), "white");
+
+    let q = About::Text(String::from("What is your favorite color?"));
+    assert_eq!(q.tell_me(), "I don't have an answer");
 }
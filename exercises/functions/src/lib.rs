@@ -137,3 +137,84 @@ fn closures() {
     let scaled_add = |a, b| (a + b) * factor;
     println!("(3 + 4) * 2 = {}", scaled_add(3, 4));
 }
+
+/// # Higher-Order Functions
+///
+/// **A higher-order function takes a function (or a closure) as parameter.**
+///
+/// The parameter is generic, bounded by one of the closure traits:
+/// - `Fn` - the closure only reads its environment
+/// - `FnMut` - the closure modifies its environment
+/// - `FnOnce` - the closure can only be called once
+///
+/// Returns the item with the greatest key, computed by `key`.
+/// If several items have the greatest key the last one is returned
+/// and `None` is returned if there is no item at all.
+pub fn max_by_key<T, K: Ord, F: Fn(&T) -> K>(items: &[T], key: F) -> Option<&T> {
+    // iterators already provide this, we just forward
+    // the closure to the iterator method
+    items.iter().max_by_key(|item| key(item))
+}
+
+#[test]
+fn higher_order_functions() {
+    #[derive(Debug, PartialEq)]
+    struct Person {
+        name: &'static str,
+        age: u8,
+    }
+
+    let people = [
+        Person {
+            name: "Alice",
+            age: 42,
+        },
+        Person {
+            name: "Bob",
+            age: 67,
+        },
+        Person {
+            name: "Carol",
+            age: 12,
+        },
+    ];
+
+    let oldest = max_by_key(&people, |p| p.age);
+    assert_eq!(oldest.map(|p| p.name), Some("Bob"));
+
+    let words = ["hi", "hello", "hey"];
+    assert_eq!(max_by_key(&words, |w| w.len()), Some(&"hello"));
+
+    let empty: [i32; 0] = [];
+    assert_eq!(max_by_key(&empty, |i| *i), None);
+}
+
+/// # Returning Closures
+///
+/// Every closure has its own anonymous type, so we cannot name
+/// it in a return type. `impl Fn(A) -> C` means "some type
+/// implementing `Fn(A) -> C`", the compiler knows which one.
+///
+/// Returns a closure applying `f` then `g` to its parameter.
+/// `move` is needed as the returned closure must own `f` and `g`,
+/// they would be dropped at the end of `compose` otherwise.
+pub fn compose<A, B, C>(f: impl Fn(A) -> B, g: impl Fn(B) -> C) -> impl Fn(A) -> C {
+    move |x| g(f(x))
+}
+
+#[test]
+fn returning_closures() {
+    let add_one = |x| x + 1;
+    let double = |x| x * 2;
+
+    let add_then_double = compose(add_one, double);
+    assert_eq!(add_then_double(3), 8);
+
+    let double_then_add = compose(double, add_one);
+    assert_eq!(double_then_add(3), 7);
+
+    // types can change along the way
+    let len_is_even = compose(|s: &str| s.len(), |len| len % 2 == 0);
+    assert!(len_is_even("rust"));
+    assert!(!len_is_even("rusty"));
+}
@@ -63,6 +63,71 @@ fn generics_in_functions_2() {
     //}
 }
 
+/// Here is a working version of `largest`: comparing items with `>`
+/// is only possible for types implementing `PartialOrd`, so we have
+/// to tell the compiler `T` must implement it with a TRAIT BOUND.
+///
+/// Indexing an empty slice would panic so we return an `Option`
+/// instead, `None` meaning there is no largest element.
+pub fn largest<T: PartialOrd>(list: &[T]) -> Option<&T> {
+    let mut largest = list.first()?;
+
+    for item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+
+    Some(largest)
+}
+
+#[test]
+fn generics_largest() {
+    assert_eq!(largest(&[34, 50, 25, 100, 65]), Some(&100));
+    assert_eq!(largest(&[-3, -1, -2]), Some(&-1));
+    assert_eq!(largest(&[1.5, 0.2, 4.2, 3.9]), Some(&4.2));
+    assert_eq!(largest(&['y', 'm', 'a', 'q']), Some(&'y'));
+
+    let empty: &[i32] = &[];
+    assert_eq!(largest(empty), None);
+
+    assert_eq!(largest(&[42]), Some(&42));
+}
+
+/// Returns the smallest and the largest items of the slice, in a
+/// single pass, or `None` if the slice is empty.
+///
+/// `T: Copy` lets us return the items by value rather than by
+/// reference as `largest` does.
+pub fn min_max<T: PartialOrd + Copy>(slice: &[T]) -> Option<(T, T)> {
+    // with a single item, it is both the min and the max
+    let first = *slice.first()?;
+    let (mut min, mut max) = (first, first);
+
+    for &item in &slice[1..] {
+        if item < min {
+            min = item;
+        } else if item > max {
+            max = item;
+        }
+    }
+
+    Some((min, max))
+}
+
+#[test]
+fn generics_min_max() {
+    assert_eq!(min_max(&[34, 50, 25, 100, 65]), Some((25, 100)));
+    assert_eq!(min_max(&[-3, -1, -2]), Some((-3, -1)));
+    assert_eq!(min_max(&[1.5, 0.2, 4.2, 3.9]), Some((0.2, 4.2)));
+
+    let empty: &[f64] = &[];
+    assert_eq!(min_max(empty), None);
+
+    assert_eq!(min_max(&[42]), Some((42, 42)));
+    assert_eq!(min_max(&['b', 'b']), Some(('b', 'b')));
+}
+
 #[test]
 fn generic_in_structure() {
     #[derive(Debug)]
@@ -83,6 +148,39 @@ fn generic_in_structure() {
     // EXERCISE: fix Point structure so that it works `mixed` assignment work
 }
 
+/// A possible fix for the exercise above: giving each field
+/// its own generic type lets `x` and `y` have different types.
+#[test]
+fn generic_in_structure_fixed() {
+    #[derive(Debug, PartialEq)]
+    struct Point<X, Y> {
+        x: X,
+        y: Y,
+    }
+
+    // swapping the fields only makes sense if they have the
+    // same type, so the method is only implemented for the
+    // points where X = Y
+    impl<T> Point<T, T> {
+        fn swap(&mut self) {
+            std::mem::swap(&mut self.x, &mut self.y);
+        }
+    }
+
+    let mixed: Point<i32, f64> = Point { x: 1, y: 4.0 };
+    println!("mixed={mixed:?}");
+    assert_eq!(mixed.x, 1);
+    assert_eq!(mixed.y, 4.0);
+
+    // EXERCISE: uncomment below code and understand what's wrong
+    // mixed.swap();
+
+    let mut integer: Point<i32, i32> = Point { x: 5, y: 10 };
+    integer.swap();
+    println!("swapped integer={integer:?}");
+    assert_eq!(integer, Point { x: 10, y: 5 });
+}
+
 #[test]
 fn generic_in_structure_2() {
     // We can use as many different generics as we want
@@ -212,6 +310,226 @@ fn generic_in_practice() {
     // EXERCISE: create an example of use of a Fifo with integers
 }
 
+/// A reusable last in first out container: the last element
+/// pushed is the first one popped.
+#[derive(Debug)]
+pub struct Stack<T> {
+    // the top of the stack is the end of the Vec
+    elements: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Self {
+            elements: Vec::new(),
+        }
+    }
+
+    /// Push an item on top of the Stack
+    pub fn push(&mut self, item: T) {
+        self.elements.push(item);
+    }
+
+    /// Pop the item on top of the Stack
+    pub fn pop(&mut self) -> Option<T> {
+        self.elements.pop()
+    }
+
+    /// Returns a reference to the item on top of the Stack
+    pub fn peek(&self) -> Option<&T> {
+        self.elements.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Display can only be implemented if the elements can be
+// displayed too, so we restrict the impl to T: Display.
+//
+// The Stack is displayed from bottom to top: [1, 2, 3]
+impl<T: std::fmt::Display> std::fmt::Display for Stack<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, e) in self.elements.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{e}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[test]
+fn generic_stack() {
+    let mut int_stack = Stack::new();
+    assert!(int_stack.is_empty());
+    assert_eq!(int_stack.pop(), None);
+    assert_eq!(int_stack.to_string(), "[]");
+
+    int_stack.push(1);
+    int_stack.push(2);
+    int_stack.push(3);
+    assert_eq!(int_stack.len(), 3);
+    assert_eq!(int_stack.peek(), Some(&3));
+    assert_eq!(int_stack.to_string(), "[1, 2, 3]");
+
+    assert_eq!(int_stack.pop(), Some(3));
+    assert_eq!(int_stack.pop(), Some(2));
+    assert_eq!(int_stack.to_string(), "[1]");
+
+    let mut string_stack = Stack::new();
+    string_stack.push(String::from("hello"));
+    string_stack.push(String::from("world !"));
+    assert_eq!(string_stack.to_string(), "[hello, world !]");
+    assert_eq!(string_stack.pop().as_deref(), Some("world !"));
+    assert_eq!(string_stack.len(), 1);
+}
+
+/// Generics are not only types: CONST GENERICS are values known at
+/// compile time. Here the dimensions are part of the type, so
+/// a `Matrix<i32, 2, 3>` and a `Matrix<i32, 3, 2>` are different
+/// types and the compiler can check dimensions for us.
+#[derive(Debug, PartialEq)]
+pub struct Matrix<T, const R: usize, const C: usize> {
+    rows: [[T; C]; R],
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn new(rows: [[T; C]; R]) -> Self {
+        Self { rows }
+    }
+
+    /// Returns the element at `row` and `col`, or `None` if
+    /// out of the matrix.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.rows.get(row)?.get(col)
+    }
+
+    /// Replaces the element at `row` and `col` and returns the
+    /// previous one, or `None` if out of the matrix.
+    pub fn set(&mut self, row: usize, col: usize, value: T) -> Option<T> {
+        let e = self.rows.get_mut(row)?.get_mut(col)?;
+        Some(std::mem::replace(e, value))
+    }
+
+    /// Applies `f` to every element, the result can be of another type.
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> Matrix<U, R, C> {
+        Matrix {
+            rows: self.rows.map(|row| row.map(&mut f)),
+        }
+    }
+}
+
+// transposing a matrix swaps its dimensions, by implementing it
+// only for Matrix<T, N, N> we restrict it to square matrices
+impl<T: Clone, const N: usize> Matrix<T, N, N> {
+    pub fn transpose(&self) -> Self {
+        Self {
+            rows: std::array::from_fn(|i| std::array::from_fn(|j| self.rows[j][i].clone())),
+        }
+    }
+}
+
+#[test]
+fn const_generic_matrix() {
+    let mut m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+    assert_eq!(m.get(0, 0), Some(&1));
+    assert_eq!(m.get(1, 2), Some(&6));
+    assert_eq!(m.get(2, 0), None);
+    assert_eq!(m.get(0, 3), None);
+
+    assert_eq!(m.set(1, 1, 42), Some(5));
+    assert_eq!(m.get(1, 1), Some(&42));
+    assert_eq!(m.set(5, 5, 1337), None);
+
+    let m = m.map(|e| e.to_string());
+    assert_eq!(m.get(1, 1).map(String::as_str), Some("42"));
+
+    let square = Matrix::new([[1, 2], [3, 4]]);
+    assert_eq!(square.transpose(), Matrix::new([[1, 3], [2, 4]]));
+    assert_eq!(square.transpose().transpose(), square);
+
+    // EXERCISE: uncomment below and understand the error
+    // Matrix::new([[1, 2, 3], [4, 5, 6]]).transpose();
+}
+
+/// Returns the consecutive overlapping pairs of the slice:
+/// `[1, 2, 3]` gives `[(1, 2), (2, 3)]`.
+///
+/// Each element is part of two pairs, so we need `T: Clone`
+/// to own it twice.
+pub fn pair_up<T: Clone>(slice: &[T]) -> Vec<(T, T)> {
+    slice
+        .windows(2)
+        .map(|w| (w[0].clone(), w[1].clone()))
+        .collect()
+}
+
+#[test]
+fn generic_pair_up() {
+    assert_eq!(pair_up(&[1, 2, 3]), [(1, 2), (2, 3)]);
+    assert_eq!(
+        pair_up(&["a", "b", "c", "d"]),
+        [("a", "b"), ("b", "c"), ("c", "d")]
+    );
+    assert_eq!(pair_up(&[42]), []);
+
+    let empty: &[String] = &[];
+    assert!(pair_up(empty).is_empty());
+}
+
+/// # Operator Overloading
+///
+/// Operators are traits too: `a + b` is just `Add::add(a, b)`.
+/// Implementing `std::ops::Add` for our type lets us use `+` on it.
+#[derive(Debug, PartialEq)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+// we can only add two points if we can add their coordinates,
+// `Output = T` makes sure adding two T gives a T back
+impl<T: std::ops::Add<Output = T>> std::ops::Add for Point<T> {
+    type Output = Point<T>;
+
+    // add takes both points by value so no need for T: Copy
+    fn add(self, other: Self) -> Self::Output {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+#[test]
+fn generic_operator_overloading() {
+    assert_eq!(
+        Point { x: 1, y: 2 } + Point { x: 3, y: 4 },
+        Point { x: 4, y: 6 }
+    );
+    assert_eq!(
+        Point { x: 1.5, y: 2.0 } + Point { x: 0.5, y: -1.0 },
+        Point { x: 2.0, y: 1.0 }
+    );
+
+    // EXERCISE: uncomment below and understand the error
+    // let _ = Point { x: "hello", y: "world" } + Point { x: "!", y: "!" };
+}
+
 // !!! IMPORTANT !!!
 // Generic enums work similarly to generic structs in terms of:
 // - Syntax for declaring generic types (`enum Name<T> { ... }`)
@@ -277,3 +277,137 @@ fn question_mark_operator() {
         square_first_even_short(&[3, 1, 9, 2])
     );
 }
+
+/// The mutable sibling of `find_first_even_number`: instead of a
+/// copy of the number we get a mutable reference into the slice,
+/// so the caller can modify the element in place.
+///
+/// The returned `Option` borrows `numbers` mutably, so the slice
+/// cannot be used until we are done with the reference.
+pub fn first_even_mut(numbers: &mut [i32]) -> Option<&mut i32> {
+    numbers.iter_mut().find(|n| **n % 2 == 0)
+}
+
+#[test]
+fn option_mut_ref() {
+    let mut numbers = [3, 1, 4, 9, 6];
+    if let Some(n) = first_even_mut(&mut numbers) {
+        *n *= 2;
+    }
+    assert_eq!(numbers, [3, 1, 8, 9, 6]);
+
+    let mut odds = [1, 3, 5];
+    assert_eq!(first_even_mut(&mut odds), None);
+    assert_eq!(first_even_mut(&mut []), None);
+}
+
+/// The `User` of `option_1`, this time built with a BUILDER
+/// which validates the mandatory fields.
+#[derive(Debug)]
+pub struct User {
+    identifier: String,
+    password: String,
+    age: Option<u8>,
+}
+
+impl User {
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    pub fn age(&self) -> Option<u8> {
+        self.age
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UserError {
+    EmptyIdentifier,
+    EmptyPassword,
+}
+
+/// Builds a `User` field by field.
+///
+/// Every field is an `Option` until `build` is called: a missing
+/// or empty mandatory field makes `build` return an error, while
+/// a missing age just stays `None`.
+#[derive(Debug, Default)]
+pub struct UserBuilder {
+    identifier: Option<String>,
+    password: Option<String>,
+    age: Option<u8>,
+}
+
+impl UserBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // each setter takes and returns the builder so
+    // that calls can be chained
+    pub fn identifier(mut self, identifier: &str) -> Self {
+        self.identifier = Some(String::from(identifier));
+        self
+    }
+
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(String::from(password));
+        self
+    }
+
+    pub fn age(mut self, age: u8) -> Self {
+        self.age = Some(age);
+        self
+    }
+
+    pub fn build(self) -> Result<User, UserError> {
+        // Option::filter turns a Some holding an empty string into None
+        let identifier = self
+            .identifier
+            .filter(|s| !s.is_empty())
+            .ok_or(UserError::EmptyIdentifier)?;
+        let password = self
+            .password
+            .filter(|s| !s.is_empty())
+            .ok_or(UserError::EmptyPassword)?;
+
+        Ok(User {
+            identifier,
+            password,
+            age: self.age,
+        })
+    }
+}
+
+#[test]
+fn option_user_builder() {
+    let u1 = UserBuilder::new()
+        .identifier("toto")
+        .password("Password123")
+        .age(42)
+        .build()
+        .unwrap();
+    assert_eq!(u1.identifier(), "toto");
+    assert_eq!(u1.password(), "Password123");
+    assert_eq!(u1.age(), Some(42));
+
+    let u2 = UserBuilder::new()
+        .identifier("joe")
+        .password("0xdeadbeef")
+        .build()
+        .unwrap();
+    assert_eq!(u2.age(), None);
+
+    let res = UserBuilder::new().password("0xdeadbeef").build();
+    assert_eq!(res.unwrap_err(), UserError::EmptyIdentifier);
+
+    let res = UserBuilder::new().identifier("").password("pass").build();
+    assert_eq!(res.unwrap_err(), UserError::EmptyIdentifier);
+
+    let res = UserBuilder::new().identifier("joe").password("").build();
+    assert_eq!(res.unwrap_err(), UserError::EmptyPassword);
+}
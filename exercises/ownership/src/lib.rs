@@ -241,6 +241,54 @@ fn mut_slice() {
     println!("numbers={numbers:?}");
 }
 
+/// The error returned when an index is out of the bounds of a slice.
+#[derive(Debug, PartialEq)]
+pub struct IndexError {
+    pub index: usize,
+    pub len: usize,
+}
+
+/// Swaps the elements at indexes `i` and `j` of a mutable slice.
+///
+/// Indexing a slice out of its bounds panics, so we check both
+/// indexes first and return an error instead. `slice.swap` does
+/// the work: two `&mut` to elements of the same slice cannot
+/// exist at the same time, so `std::mem::swap(&mut slice[i], &mut
+/// slice[j])` would not compile.
+pub fn swap_in_place<T>(slice: &mut [T], i: usize, j: usize) -> Result<(), IndexError> {
+    let len = slice.len();
+    for index in [i, j] {
+        if index >= len {
+            return Err(IndexError { index, len });
+        }
+    }
+    slice.swap(i, j);
+    Ok(())
+}
+
+#[test]
+fn mut_slice_swap() {
+    let mut numbers = [1, 2, 3, 4];
+    assert_eq!(swap_in_place(&mut numbers, 0, 3), Ok(()));
+    assert_eq!(numbers, [4, 2, 3, 1]);
+
+    // swapping an element with itself does nothing
+    assert_eq!(swap_in_place(&mut numbers, 1, 1), Ok(()));
+    assert_eq!(numbers, [4, 2, 3, 1]);
+
+    let mut words = vec!["hello", "world"];
+    assert_eq!(
+        swap_in_place(&mut words, 0, 2),
+        Err(IndexError { index: 2, len: 2 })
+    );
+    assert_eq!(
+        swap_in_place(&mut words, 5, 0),
+        Err(IndexError { index: 5, len: 2 })
+    );
+    // the slice is left untouched on error
+    assert_eq!(words, ["hello", "world"]);
+}
+
 /// # Golden Rule of Slice Parameters
 ///
 /// **"If the reference you want to pass to a function is sliceable,
@@ -359,3 +407,51 @@ fn lifetime_example() {
     // - how to fix the error
     // - this function is defined for any &str (this is the issue)
 }
+
+/// The fixed version of `longest`: the LIFETIME ANNOTATION `'a`
+/// tells the compiler the returned reference lives as long as
+/// the shortest lived of `x` and `y`.
+///
+/// If both strings have the same length, `x` is returned.
+pub fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() >= y.len() { x } else { y }
+}
+
+/// Returns the longest of the strings, the first one if several
+/// have the same length, or `None` if there is no string at all.
+///
+/// Lifetime elision cannot apply here: `&[&str]` holds two
+/// references, the slice and the strings in it, so there are two
+/// input lifetimes and the compiler cannot pick one for the output
+/// (error E0106). We name `'a` the lifetime of the strings: the
+/// returned reference is one of them, it doesn't borrow the slice
+/// and can outlive it.
+pub fn longest_of<'a>(strings: &[&'a str]) -> Option<&'a str> {
+    let mut longest = *strings.first()?;
+    for s in strings {
+        if s.len() > longest.len() {
+            longest = s;
+        }
+    }
+    Some(longest)
+}
+
+#[test]
+fn lifetime_longest() {
+    let s1 = String::from("hello");
+    let s2 = String::from("hi");
+    assert_eq!(longest(&s1, &s2), "hello");
+    assert_eq!(longest(&s2, &s1), "hello");
+    assert_eq!(longest("hello", "world"), "hello");
+
+    assert_eq!(longest_of(&["a", "abc", "ab"]), Some("abc"));
+    assert_eq!(longest_of(&["one", "two", "six"]), Some("one"));
+    assert_eq!(longest_of(&[]), None);
+
+    // the result borrows s1, not the vector, so it outlives the vector
+    let longest_str = {
+        let strings = vec![s1.as_str(), s2.as_str()];
+        longest_of(&strings)
+    };
+    assert_eq!(longest_str, Some("hello"));
+}
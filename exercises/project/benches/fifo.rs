@@ -0,0 +1,69 @@
+//! Compares a naive `Vec` based Fifo, putting elements with
+//! `insert(0, _)` as done in the generics exercise, with the
+//! `VecDeque` based [`Fifo`] of this crate.
+//!
+//! Each `insert(0, _)` shifts all the elements already in the
+//! vector, so filling the naive Fifo with N elements is O(N²)
+//! while it is O(N) for the `VecDeque` one. Run with:
+//! `cargo bench --bench fifo`
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use project::{Fifo, Queue};
+
+// the Fifo of the generics exercise
+struct VecFifo<T> {
+    elements: Vec<T>,
+}
+
+impl<T> VecFifo<T> {
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            elements: Vec::with_capacity(cap),
+        }
+    }
+
+    fn put(&mut self, item: T) {
+        self.elements.insert(0, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.elements.pop()
+    }
+}
+
+fn put_then_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fifo put then pop");
+
+    for n in [100, 1_000, 10_000] {
+        group.bench_with_input(BenchmarkId::new("Vec::insert(0, _)", n), &n, |b, &n| {
+            b.iter(|| {
+                let mut fifo = VecFifo::with_capacity(n);
+                for i in 0..n {
+                    fifo.put(black_box(i));
+                }
+                while let Some(i) = fifo.pop() {
+                    black_box(i);
+                }
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("VecDeque", n), &n, |b, &n| {
+            b.iter(|| {
+                let mut fifo = Fifo::with_capacity(n);
+                for i in 0..n {
+                    fifo.put(black_box(i)).unwrap();
+                }
+                while let Some(i) = fifo.pop() {
+                    black_box(i);
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, put_then_pop);
+criterion_main!(benches);
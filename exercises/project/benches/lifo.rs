@@ -0,0 +1,94 @@
+//! Compares the `Vec` based [`Lifo`] of this crate with a `Lifo`
+//! built as a singly linked list of `Box`ed nodes.
+//!
+//! Both put and pop in O(1), and a linked list never has to move
+//! its elements to grow, so it may look like a good fit for a
+//! stack. It is not: each put allocates a node (and each pop frees
+//! one) while a `Vec` only reallocates when it has to grow, and the
+//! nodes are scattered in memory while the elements of a `Vec` are
+//! contiguous, which is what CPU caches are made for.
+//!
+//! Expect the `Vec` based `Lifo` to be several times faster at
+//! putting then popping elements, and the gap to widen as the
+//! number of elements grows. Run with: `cargo bench --bench lifo`
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use project::{Lifo, Queue};
+
+// a stack as a linked list: the head is the top of the stack
+// and each node owns the one below it
+struct LinkedLifo<T> {
+    head: Option<Box<Node<T>>>,
+}
+
+struct Node<T> {
+    item: T,
+    next: Option<Box<Node<T>>>,
+}
+
+impl<T> LinkedLifo<T> {
+    fn new() -> Self {
+        Self { head: None }
+    }
+
+    fn put(&mut self, item: T) {
+        let next = self.head.take();
+        self.head = Some(Box::new(Node { item, next }));
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let node = self.head.take()?;
+        self.head = node.next;
+        Some(node.item)
+    }
+}
+
+impl<T> Drop for LinkedLifo<T> {
+    // the default drop is recursive (dropping a node drops the next
+    // one) and would overflow the stack on long lists
+    fn drop(&mut self) {
+        let mut next = self.head.take();
+        while let Some(mut node) = next {
+            next = node.next.take();
+        }
+    }
+}
+
+fn put_then_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lifo put then pop");
+
+    for n in [1_000, 100_000] {
+        group.bench_with_input(BenchmarkId::new("linked list", n), &n, |b, &n| {
+            b.iter(|| {
+                let mut lifo = LinkedLifo::new();
+                for i in 0..n {
+                    lifo.put(black_box(i));
+                }
+                while let Some(i) = lifo.pop() {
+                    black_box(i);
+                }
+            })
+        });
+
+        // unbounded so that, as the linked list, the
+        // Vec grows as elements are put
+        group.bench_with_input(BenchmarkId::new("Vec", n), &n, |b, &n| {
+            b.iter(|| {
+                let mut lifo = Lifo::new();
+                for i in 0..n {
+                    lifo.put(black_box(i)).unwrap();
+                }
+                while let Some(i) = lifo.pop() {
+                    black_box(i);
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, put_then_pop);
+criterion_main!(benches);
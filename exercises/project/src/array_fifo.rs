@@ -0,0 +1,149 @@
+use crate::{Error, Len, Queue};
+
+/// A first in first out queue storing its elements in an
+/// array of `N` slots, so it never allocates on the heap.
+///
+/// The array is used as a ring buffer: `head` is the slot of the
+/// next element to pop and the following `len` slots (wrapping
+/// around the end of the array) hold the queued elements.
+///
+/// As the storage size is fixed at compile time, `with_capacity`
+/// panics if asked for a capacity greater than `N`. A smaller
+/// capacity is allowed and bounds the queue as usual.
+pub struct ArrayFifo<T, const N: usize> {
+    slots: [Option<T>; N],
+    // slot of the next element to pop
+    head: usize,
+    // number of elements in the queue
+    len: usize,
+    // maximum number of elements the queue can hold
+    cap: usize,
+}
+
+// is_empty is provided by the Queue trait
+#[allow(clippy::len_without_is_empty)]
+impl<T, const N: usize> ArrayFifo<T, N> {
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the maximum number of elements the queue can hold.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+}
+
+impl<T, const N: usize> Queue<T> for ArrayFifo<T, N> {
+    /// # Panics
+    ///
+    /// Panics if `cap` is greater than `N`.
+    fn with_capacity(cap: usize) -> Self {
+        assert!(
+            cap <= N,
+            "ArrayFifo capacity cannot exceed {N} (requested {cap})"
+        );
+        Self {
+            // Option<T> is not Copy so we cannot use [None; N]
+            slots: core::array::from_fn(|_| None),
+            head: 0,
+            len: 0,
+            cap,
+        }
+    }
+
+    fn peek(&self) -> Option<&T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.slots[self.head].as_ref()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let item = self.slots[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        item
+    }
+
+    fn put(&mut self, item: T) -> Result<(), Error> {
+        if self.is_full() {
+            return Err(Error::Full);
+        }
+        // the slot right after the last element, wrapping
+        // around the end of the array
+        let tail = (self.head + self.len) % N;
+        self.slots[tail] = Some(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == self.cap
+    }
+}
+
+impl<T, const N: usize> Len for ArrayFifo<T, N> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[test]
+fn array_fifo_ring_wraps() {
+    let mut fifo: ArrayFifo<i32, 3> = ArrayFifo::with_capacity(3);
+    assert!(fifo.is_empty());
+
+    for i in 0..3 {
+        assert_eq!(fifo.put(i), Ok(()));
+    }
+    assert!(fifo.is_full());
+    assert_eq!(fifo.put(3), Err(Error::Full));
+
+    assert_eq!(fifo.pop(), Some(0));
+    assert_eq!(fifo.pop(), Some(1));
+
+    // the freed slots are at the start of the array
+    // so the next elements wrap around
+    assert_eq!(fifo.put(3), Ok(()));
+    assert_eq!(fifo.put(4), Ok(()));
+    assert!(fifo.is_full());
+
+    assert_eq!(fifo.peek(), Some(&2));
+    for i in 2..5 {
+        assert_eq!(fifo.pop(), Some(i));
+    }
+    assert_eq!(fifo.pop(), None);
+    assert!(fifo.is_empty());
+}
+
+#[test]
+fn array_fifo_smaller_capacity() {
+    let mut fifo: ArrayFifo<&str, 4> = ArrayFifo::with_capacity(2);
+    assert_eq!(fifo.capacity(), 2);
+    assert_eq!(fifo.put("a"), Ok(()));
+    assert_eq!(fifo.put("b"), Ok(()));
+    assert_eq!(fifo.put("c"), Err(Error::Full));
+    assert_eq!(fifo.len(), 2);
+}
+
+#[test]
+fn array_fifo_zero_size() {
+    let mut fifo: ArrayFifo<u8, 0> = ArrayFifo::with_capacity(0);
+    assert!(fifo.is_full());
+    assert_eq!(fifo.put(1), Err(Error::Full));
+    assert_eq!(fifo.pop(), None);
+}
+
+#[test]
+#[should_panic(expected = "ArrayFifo capacity cannot exceed 2")]
+fn array_fifo_capacity_too_big() {
+    let _: ArrayFifo<u8, 2> = ArrayFifo::with_capacity(3);
+}
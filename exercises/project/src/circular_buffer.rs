@@ -0,0 +1,163 @@
+use alloc::vec::Vec;
+
+use crate::{Error, Len, Queue};
+
+/// A first in first out queue implemented as a ring buffer over a
+/// `Vec` of `cap` slots, allocated once when the queue is created.
+///
+/// This is what a `VecDeque` does under the hood: the logical order
+/// of the elements (the pop order) differs from their physical order
+/// in the vector. `head` is the slot of the next element to pop and
+/// `tail` the slot where the next element is put, both move forward
+/// and wrap around to slot `0` when passing the end of the vector:
+///
+/// ```text
+///  slots: [ d | e | _ | a | b | c ]
+///                   ^   ^
+///                tail   head         pop order: a b c d e
+/// ```
+///
+/// As `head == tail` both when the buffer is empty and when it is
+/// full, the number of elements is tracked separately.
+pub struct CircularBuffer<T> {
+    slots: Vec<Option<T>>,
+    // slot of the next element to pop
+    head: usize,
+    // slot where the next element is put
+    tail: usize,
+    // number of elements in the queue
+    len: usize,
+}
+
+// is_empty is provided by the Queue trait
+#[allow(clippy::len_without_is_empty)]
+impl<T> CircularBuffer<T> {
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the maximum number of elements the queue can hold.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    // the slot following `index`, wrapping around the end of the vector
+    fn next(&self, index: usize) -> usize {
+        (index + 1) % self.slots.len()
+    }
+}
+
+impl<T> Queue<T> for CircularBuffer<T> {
+    /// # Panics
+    ///
+    /// Panics if the `cap` slots cannot be allocated, so unlike the
+    /// other queues a capacity of `usize::MAX` is not unbounded.
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            // Option<T> is not Clone so we cannot use vec![None; cap]
+            slots: core::iter::repeat_with(|| None).take(cap).collect(),
+            head: 0,
+            tail: 0,
+            len: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<&T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.slots[self.head].as_ref()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let item = self.slots[self.head].take();
+        self.head = self.next(self.head);
+        self.len -= 1;
+        item
+    }
+
+    fn put(&mut self, item: T) -> Result<(), Error> {
+        if self.is_full() {
+            return Err(Error::Full);
+        }
+        self.slots[self.tail] = Some(item);
+        self.tail = self.next(self.tail);
+        self.len += 1;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == self.slots.len()
+    }
+}
+
+impl<T> Len for CircularBuffer<T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[test]
+fn circular_buffer_wraps_around() {
+    let mut buffer = CircularBuffer::with_capacity(4);
+    for i in 0..4 {
+        assert_eq!(buffer.put(i), Ok(()));
+    }
+    assert!(buffer.is_full());
+    assert_eq!(buffer.put(4), Err(Error::Full));
+
+    assert_eq!(buffer.pop(), Some(0));
+    assert_eq!(buffer.pop(), Some(1));
+    assert_eq!(buffer.pop(), Some(2));
+
+    // the tail is back at slot 0, the next elements are
+    // physically before the remaining one
+    for i in 4..7 {
+        assert_eq!(buffer.put(i), Ok(()));
+    }
+    assert!(buffer.is_full());
+    assert_eq!(buffer.tail, 3);
+    assert_eq!(buffer.head, 3);
+
+    // but they are still popped after it
+    assert_eq!(buffer.peek(), Some(&3));
+    for i in 3..7 {
+        assert_eq!(buffer.pop(), Some(i));
+    }
+    assert_eq!(buffer.pop(), None);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn circular_buffer_many_laps() {
+    let mut buffer = CircularBuffer::with_capacity(3);
+    let mut popped = Vec::new();
+    for i in 0..100 {
+        assert_eq!(buffer.put(i), Ok(()));
+        if buffer.is_full() {
+            popped.push(buffer.pop().unwrap());
+            popped.push(buffer.pop().unwrap());
+        }
+    }
+    while let Some(i) = buffer.pop() {
+        popped.push(i);
+    }
+    assert_eq!(popped, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn circular_buffer_zero_capacity() {
+    let mut buffer: CircularBuffer<u8> = CircularBuffer::with_capacity(0);
+    assert!(buffer.is_full());
+    assert_eq!(buffer.put(1), Err(Error::Full));
+    assert_eq!(buffer.pop(), None);
+    assert_eq!(buffer.capacity(), 0);
+}
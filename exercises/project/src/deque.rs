@@ -0,0 +1,168 @@
+use alloc::collections::VecDeque;
+
+use crate::{Error, Len, MAX_PREALLOCATED};
+
+/// A double-ended queue: elements can be put and popped
+/// at both ends.
+///
+/// Both queue kinds are special cases of a deque:
+///    - a `Fifo` only pushes at the back and pops at the front
+///    - a `Lifo` only pushes at the back and pops at the back
+///
+/// Like the other queues, a deque is bounded by the
+/// capacity given to `with_capacity`.
+pub struct Deque<T> {
+    elements: VecDeque<T>,
+    // maximum number of elements the deque can hold
+    cap: usize,
+}
+
+impl<T> Deque<T> {
+    /// Creates an empty deque able to hold at most `cap` elements.
+    ///
+    /// Memory is allocated upfront for at most 1024 elements, the
+    /// storage then grows as elements are put so that a huge `cap`
+    /// can be used as a mere bound.
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            // cap may be too big to be allocated, see MAX_PREALLOCATED
+            elements: VecDeque::with_capacity(cap.min(MAX_PREALLOCATED)),
+            cap,
+        }
+    }
+
+    /// Puts the item at the front of the deque.
+    ///
+    /// Returns [`Error::Full`] if the deque is already full.
+    pub fn push_front(&mut self, item: T) -> Result<(), Error> {
+        if self.is_full() {
+            return Err(Error::Full);
+        }
+        self.elements.push_front(item);
+        Ok(())
+    }
+
+    /// Puts the item at the back of the deque.
+    ///
+    /// Returns [`Error::Full`] if the deque is already full.
+    pub fn push_back(&mut self, item: T) -> Result<(), Error> {
+        if self.is_full() {
+            return Err(Error::Full);
+        }
+        self.elements.push_back(item);
+        Ok(())
+    }
+
+    /// Removes and returns the element at the front of the deque.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.elements.pop_front()
+    }
+
+    /// Removes and returns the element at the back of the deque.
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.elements.pop_back()
+    }
+
+    /// Returns a reference to the element at the front of the deque.
+    pub fn peek_front(&self) -> Option<&T> {
+        self.elements.front()
+    }
+
+    /// Returns a reference to the element at the back of the deque.
+    pub fn peek_back(&self) -> Option<&T> {
+        self.elements.back()
+    }
+
+    /// Returns the number of elements in the deque.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns the maximum number of elements the deque can hold.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Returns `true` if the deque holds no element.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns `true` exactly when the deque holds as many
+    /// elements as its capacity.
+    pub fn is_full(&self) -> bool {
+        self.elements.len() == self.cap
+    }
+}
+
+impl<T> Len for Deque<T> {
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+}
+
+#[test]
+fn deque_both_ends() {
+    let mut deque = Deque::with_capacity(4);
+    assert!(deque.is_empty());
+    assert_eq!(deque.peek_front(), None);
+    assert_eq!(deque.peek_back(), None);
+
+    assert_eq!(deque.push_back(2), Ok(()));
+    assert_eq!(deque.push_front(1), Ok(()));
+    assert_eq!(deque.push_back(3), Ok(()));
+    assert_eq!(deque.push_front(0), Ok(()));
+    assert_eq!(deque.len(), 4);
+    assert_eq!(Len::len(&deque), 4);
+
+    assert_eq!(deque.peek_front(), Some(&0));
+    assert_eq!(deque.peek_back(), Some(&3));
+
+    assert_eq!(deque.pop_front(), Some(0));
+    assert_eq!(deque.pop_back(), Some(3));
+    assert_eq!(deque.pop_back(), Some(2));
+    assert_eq!(deque.pop_front(), Some(1));
+
+    assert_eq!(deque.pop_front(), None);
+    assert_eq!(deque.pop_back(), None);
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn deque_as_fifo_and_lifo() {
+    let mut deque = Deque::with_capacity(3);
+    for i in 1..=3 {
+        assert_eq!(deque.push_back(i), Ok(()));
+    }
+    // Fifo: push at the back, pop at the front
+    assert_eq!(deque.pop_front(), Some(1));
+
+    // Lifo: push at the back, pop at the back
+    assert_eq!(deque.push_back(4), Ok(()));
+    assert_eq!(deque.pop_back(), Some(4));
+}
+
+#[test]
+fn deque_huge_capacity_is_not_allocated() {
+    let cap = usize::MAX - 1;
+    let mut deque: Deque<u8> = Deque::with_capacity(cap);
+    assert_eq!(deque.capacity(), cap);
+    assert_eq!(deque.push_front(1), Ok(()));
+    assert!(!deque.is_full());
+}
+
+#[test]
+fn deque_full() {
+    let mut deque = Deque::with_capacity(2);
+    assert_eq!(deque.push_front(1), Ok(()));
+    assert_eq!(deque.push_back(2), Ok(()));
+    assert!(deque.is_full());
+
+    assert_eq!(deque.push_front(0), Err(Error::Full));
+    assert_eq!(deque.push_back(3), Err(Error::Full));
+
+    // rejected items did not make it in the deque
+    assert_eq!(deque.len(), deque.capacity());
+    assert_eq!(deque.pop_front(), Some(1));
+    assert_eq!(deque.pop_front(), Some(2));
+}
@@ -0,0 +1,1630 @@
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::ops::{Index, IndexMut};
+
+use crate::{Error, Len, Lifo, MAX_PREALLOCATED, Queue};
+
+/// A first in first out queue: elements are popped
+/// in the same order they have been put.
+///
+/// Elements are stored in a [`VecDeque`], a growable ring buffer.
+/// A `Vec` can only add or remove elements cheaply at its end, so
+/// a `Vec` based Fifo must either `insert(0, _)` on put or `remove(0)`
+/// on pop, both shifting every other element: O(n) per operation.
+/// A `VecDeque` can push and pop at both ends in O(1) (amortized for
+/// push), which is exactly what a Fifo needs.
+#[derive(Clone)]
+pub struct Fifo<T> {
+    // the oldest element is at the front of the deque
+    // and new elements are pushed at the back
+    elements: VecDeque<T>,
+    // maximum number of elements the queue can hold
+    cap: usize,
+    // when true, putting in a full queue evicts the oldest
+    // element instead of failing
+    overwrite: bool,
+    // when set, the queue is also bounded by the total
+    // weight of its elements (see `with_weight_limit`)
+    weight: Option<WeightLimit<T>>,
+}
+
+// the maximum total weight of the elements of a queue and
+// the function giving the weight of an element
+struct WeightLimit<T> {
+    max: usize,
+    // an Arc (rather than a Box) keeps the queue Clone and the
+    // Send + Sync bounds keep it usable from a SyncQueue
+    weigh: Arc<dyn Fn(&T) -> usize + Send + Sync>,
+    // running total weight of the elements, kept up to date by
+    // put and pop so they don't have to weigh every element;
+    // None when it is stale: when the elements may have been
+    // modified in place (peek_mut, IndexMut...) or removed by
+    // other means than pop
+    total: Option<usize>,
+}
+
+// derive(Clone) would needlessly require T: Clone
+impl<T> Clone for WeightLimit<T> {
+    fn clone(&self) -> Self {
+        Self {
+            max: self.max,
+            weigh: Arc::clone(&self.weigh),
+            total: self.total,
+        }
+    }
+}
+
+impl<T> WeightLimit<T> {
+    // the total weight of `items`, used when the running total is stale
+    fn weigh_all<'a>(&self, items: impl Iterator<Item = &'a T>) -> usize
+    where
+        T: 'a,
+    {
+        items
+            .map(|item| (self.weigh)(item))
+            .fold(0, usize::saturating_add)
+    }
+}
+
+// is_empty is provided by the Queue trait
+#[allow(clippy::len_without_is_empty)]
+impl<T> Fifo<T> {
+    /// Creates an empty queue without capacity bound.
+    ///
+    /// Its capacity is `usize::MAX`, a number of elements that cannot
+    /// fit in memory: the queue is never full and `put` never fails.
+    /// Nothing is allocated upfront, `with_capacity(usize::MAX)` is
+    /// the same.
+    pub fn new() -> Self {
+        Self {
+            elements: VecDeque::new(),
+            cap: usize::MAX,
+            overwrite: false,
+            weight: None,
+        }
+    }
+
+    /// Creates an empty queue able to hold at most `cap` elements
+    /// which, once full, makes room for new elements by evicting
+    /// the oldest ones: the queue keeps the latest `cap` elements.
+    ///
+    /// With the default mode (see `with_capacity`) putting in a full
+    /// queue fails with [`Error::Full`] and the new item is dropped,
+    /// while in this mode `put` always succeeds and it is the oldest
+    /// element that is dropped. Use `put_overwrite` to get the
+    /// evicted element back.
+    ///
+    /// NB: `try_put` doesn't evict, it always hands the item
+    /// back if the queue is full.
+    pub fn with_capacity_overwrite(cap: usize) -> Self {
+        Self {
+            overwrite: true,
+            ..Self::with_capacity(cap)
+        }
+    }
+
+    /// Creates an empty queue bounded by the total weight of its
+    /// elements rather than by their number, `weigh` giving the
+    /// weight of an element (e.g. its size in bytes).
+    ///
+    /// The queue is full once the total weight reaches `max_weight`
+    /// and `put` fails with [`Error::Full`] if the item would make
+    /// the total weight exceed `max_weight`, so `put` may fail on a
+    /// queue that is not full yet if the item is too heavy.
+    ///
+    /// The number of elements is not bounded: the capacity is
+    /// `usize::MAX` as with `new`.
+    ///
+    /// The total weight is kept up to date as elements are put and
+    /// popped, so `weigh` is called once per element put or popped.
+    /// It is only computed again, weighing all the elements, after
+    /// they have been accessed mutably (e.g. with `peek_mut`).
+    pub fn with_weight_limit(
+        max_weight: usize,
+        weigh: impl Fn(&T) -> usize + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            weight: Some(WeightLimit {
+                max: max_weight,
+                weigh: Arc::new(weigh),
+                total: Some(0),
+            }),
+            ..Self::new()
+        }
+    }
+
+    /// Returns the total weight of the elements of a queue created
+    /// with `with_weight_limit`, or `None` for other queues.
+    pub fn total_weight(&self) -> Option<usize> {
+        let limit = self.weight.as_ref()?;
+        Some(
+            limit
+                .total
+                .unwrap_or_else(|| limit.weigh_all(self.elements.iter())),
+        )
+    }
+
+    // returns false if putting `item` would exceed the weight limit,
+    // otherwise adds its weight to the running total as the caller
+    // is about to put it; always true without weight limit
+    fn add_weight(&mut self, item: &T) -> bool {
+        let Some(limit) = &mut self.weight else {
+            return true;
+        };
+        let total = match limit.total {
+            Some(total) => total,
+            None => limit.weigh_all(self.elements.iter()),
+        };
+        // whatever the result the total is now up to date
+        limit.total = Some(total);
+        match total.checked_add((limit.weigh)(item)) {
+            Some(new_total) if new_total <= limit.max => {
+                limit.total = Some(new_total);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // removes the weight of a popped `item` from the running total
+    fn remove_weight(&mut self, item: &T) {
+        if let Some(limit) = &mut self.weight
+            && let Some(total) = &mut limit.total
+        {
+            *total = total.saturating_sub((limit.weigh)(item));
+        }
+    }
+
+    // marks the running total as stale, to be called whenever the
+    // elements are modified or removed other than with put and pop
+    fn invalidate_weight(&mut self) {
+        if let Some(limit) = &mut self.weight {
+            limit.total = None;
+        }
+    }
+
+    // an unbounded queue is never full, even if its length could
+    // somehow reach usize::MAX (e.g. with zero sized elements)
+    fn is_unbounded(&self) -> bool {
+        self.cap == usize::MAX
+    }
+
+    /// Creates a full queue of capacity `cap` holding `cap`
+    /// default values of `T`.
+    pub fn filled_with_default(cap: usize) -> Self
+    where
+        T: Default,
+    {
+        Self {
+            elements: core::iter::repeat_with(T::default).take(cap).collect(),
+            cap,
+            overwrite: false,
+            weight: None,
+        }
+    }
+
+    /// Creates a queue of capacity `cap` holding the elements of
+    /// `vec`, the first element of the vector is the first to pop.
+    ///
+    /// Returns [`Error::Capacity`] with the length of `vec` if it
+    /// has more than `cap` elements.
+    pub fn try_from_with_capacity(vec: Vec<T>, cap: usize) -> Result<Self, Error> {
+        if vec.len() > cap {
+            return Err(Error::Capacity(vec.len()));
+        }
+        Ok(Self {
+            // converting a Vec into a VecDeque doesn't reallocate
+            elements: VecDeque::from(vec),
+            cap,
+            overwrite: false,
+            weight: None,
+        })
+    }
+
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns the maximum number of elements the queue can hold.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Returns an iterator over references to the elements,
+    /// in the order they would be popped.
+    ///
+    /// The iterator is double ended, so `iter().rev()` walks
+    /// the elements in reverse pop order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.elements.iter()
+    }
+
+    /// Removes all the elements from the queue and returns them
+    /// as an iterator, in the order they would be popped.
+    ///
+    /// The queue is empty once the iterator is dropped, even
+    /// if it has not been fully consumed.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.invalidate_weight();
+        self.elements.drain(..)
+    }
+
+    /// Puts the item in the queue, like `put`, but hands the
+    /// item back in `Err` if the queue is full so that the
+    /// caller keeps ownership of it.
+    pub fn try_put(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() || !self.add_weight(&item) {
+            return Err(item);
+        }
+        self.elements.push_back(item);
+        Ok(())
+    }
+
+    /// Returns a mutable reference to the next element to pop
+    /// (the front of the queue), like `peek` but allowing to
+    /// modify the element without removing it.
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.invalidate_weight();
+        self.elements.front_mut()
+    }
+
+    /// Removes all the elements from the queue, its
+    /// capacity is left unchanged.
+    pub fn clear(&mut self) {
+        self.invalidate_weight();
+        self.elements.clear();
+    }
+
+    /// Makes room for at least `additional` more elements: the
+    /// capacity is raised to `len() + additional` if it is lower
+    /// and the memory for those elements is allocated upfront.
+    ///
+    /// Returns [`Error::Capacity`] if `len() + additional` overflows
+    /// a `usize` or if the memory cannot be allocated, in which case
+    /// the queue is left unchanged.
+    pub fn reserve(&mut self, additional: usize) -> Result<(), Error> {
+        // a plain addition would panic in debug and silently
+        // wrap around in release, giving a too small capacity
+        let required = self
+            .elements
+            .len()
+            .checked_add(additional)
+            .ok_or(Error::Capacity(usize::MAX))?;
+        self.elements
+            .try_reserve(additional)
+            .map_err(|_| Error::Capacity(required))?;
+        self.cap = self.cap.max(required);
+        Ok(())
+    }
+
+    /// Returns `true` if the queue contains an element equal to `item`.
+    pub fn contains(&self, item: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.elements.contains(item)
+    }
+
+    /// Keeps only the elements for which `f` returns `true`,
+    /// the order of the remaining elements is preserved.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.invalidate_weight();
+        self.elements.retain(f);
+    }
+
+    /// Puts the item in the queue and returns the element evicted
+    /// to make room for it, if any.
+    ///
+    /// If the queue is full:
+    ///    - in overwrite mode (see `with_capacity_overwrite`) the
+    ///      oldest element is evicted and returned in `Ok(Some(_))`
+    ///    - otherwise [`Error::Full`] is returned and the item dropped
+    pub fn put_overwrite(&mut self, item: T) -> Result<Option<T>, Error> {
+        if !self.is_full() && self.add_weight(&item) {
+            self.elements.push_back(item);
+            return Ok(None);
+        }
+
+        if !self.overwrite {
+            return Err(Error::Full);
+        }
+
+        // with a capacity of 0 there is nothing to evict so
+        // the new item is the one evicted right away
+        if self.elements.is_empty() {
+            return Ok(Some(item));
+        }
+        self.invalidate_weight();
+        let evicted = self.elements.pop_front();
+        self.elements.push_back(item);
+        Ok(evicted)
+    }
+
+    /// Converts the queue into a [`Lifo`] with the same capacity
+    /// and popping the remaining elements in the same order.
+    pub fn into_lifo(self) -> Lifo<T> {
+        let mut lifo = Lifo::with_capacity(self.cap);
+        // the next element to pop must be on top of the
+        // stack, so it has to be put last
+        lifo.extend(self.elements.into_iter().rev());
+        lifo
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The elements of `other` are popped after the ones
+    /// already in `self`.
+    ///
+    /// Returns [`Error::Full`] if `self` cannot hold all the elements,
+    /// in which case neither queue is modified.
+    pub fn append(&mut self, other: &mut Self) -> Result<(), Error> {
+        match self.elements.len().checked_add(other.elements.len()) {
+            Some(len) if len <= self.cap => {}
+            // an overflowing length cannot fit either
+            _ => return Err(Error::Full),
+        }
+        if let Some(limit) = &self.weight {
+            let all = self.elements.iter().chain(other.elements.iter());
+            if limit.weigh_all(all) > limit.max {
+                return Err(Error::Full);
+            }
+        }
+        self.invalidate_weight();
+        other.invalidate_weight();
+        self.elements.append(&mut other.elements);
+        Ok(())
+    }
+
+    /// Returns the elements as a slice, in pop order.
+    ///
+    /// The elements of a `VecDeque` may wrap around the end of its
+    /// buffer, so they are first moved to be contiguous in memory,
+    /// which is why this needs `&mut self`.
+    pub fn as_slice(&mut self) -> &[T] {
+        self.elements.make_contiguous()
+    }
+
+    /// Returns the elements as a mutable slice, in pop order.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.invalidate_weight();
+        self.elements.make_contiguous()
+    }
+
+    /// Consumes the queue and returns a queue of the same capacity
+    /// holding the result of `f` applied to each element, the pop
+    /// order is preserved.
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Fifo<U> {
+        Fifo {
+            elements: self.elements.into_iter().map(f).collect(),
+            cap: self.cap,
+            overwrite: self.overwrite,
+            // the weigh function takes a &T, not a &U
+            weight: None,
+        }
+    }
+
+    /// Returns references to the next `n` elements to pop, in pop
+    /// order, or to all the elements if there are less than `n`.
+    pub fn peek_n(&self, n: usize) -> Vec<&T> {
+        self.iter().take(n).collect()
+    }
+
+    /// Pops up to `n` elements and returns them in pop order,
+    /// stopping early if the queue gets empty.
+    pub fn pop_n(&mut self, n: usize) -> Vec<T> {
+        self.invalidate_weight();
+        let n = n.min(self.elements.len());
+        self.elements.drain(..n).collect()
+    }
+
+    /// Releases the memory allocated for elements beyond `len()`.
+    ///
+    /// NB: this is about allocation only, the logical bound
+    /// returned by `capacity()` is left unchanged.
+    pub fn shrink_to_fit(&mut self) {
+        self.elements.shrink_to_fit();
+    }
+
+    /// Returns how many more elements fit in the memory already
+    /// allocated, i.e. before putting an element reallocates.
+    ///
+    /// This is distinct from `capacity() - len()`, the number of
+    /// elements the queue accepts before being full: memory is
+    /// allocated as elements are put.
+    pub fn spare_capacity(&self) -> usize {
+        self.elements.capacity() - self.elements.len()
+    }
+
+    /// Rotates the queue so that the element at position `mid`
+    /// in pop order becomes the next one to pop, the elements
+    /// before it being moved, in order, after the last one.
+    ///
+    /// `mid` is taken modulo `len()`, so rotating by `len()`
+    /// leaves the queue unchanged.
+    pub fn rotate_left(&mut self, mid: usize) {
+        let len = self.elements.len();
+        if len == 0 {
+            return;
+        }
+        self.elements.rotate_left(mid % len);
+    }
+
+    /// Removes consecutive equal elements, in pop order, only the
+    /// first element of each run is kept (like `Vec::dedup`).
+    pub fn dedup_consecutive(&mut self)
+    where
+        T: PartialEq,
+    {
+        // VecDeque has no dedup, converting from and into a Vec
+        // doesn't reallocate
+        self.invalidate_weight();
+        let mut elements = Vec::from(core::mem::take(&mut self.elements));
+        elements.dedup();
+        self.elements = VecDeque::from(elements);
+    }
+
+    /// Returns the smallest element of the queue, or `None` if it is empty.
+    ///
+    /// NB: it is not named `min` as queues of `Ord` elements are
+    /// `Ord` themselves, which already gives them `Ord::min` to get
+    /// the smallest of two queues.
+    pub fn min_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().min()
+    }
+
+    /// Returns the largest element of the queue, or `None` if it is empty.
+    ///
+    /// NB: not named `max` to not clash with `Ord::max`, see `min_element`.
+    pub fn max_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().max()
+    }
+
+    /// Returns a copy of the elements in pop order, without
+    /// modifying the queue (unlike `Vec::from` which consumes it).
+    pub fn to_vec_pop_order(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Folds the elements, in pop order, into an accumulator
+    /// without consuming the queue (see `Iterator::fold`).
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    /// Consumes the queue and returns a queue holding only the
+    /// elements for which `pred` returns `true`, in the same order.
+    ///
+    /// This is the owning counterpart of `retain`, the capacity of
+    /// the returned queue is the number of elements kept.
+    pub fn filter_into<F: FnMut(&T) -> bool>(mut self, pred: F) -> Self {
+        self.retain(pred);
+        self.cap = self.elements.len();
+        self
+    }
+
+    /// Splits the queue in two: `self` keeps the first `at` elements,
+    /// in pop order, and the remaining ones are returned in a new
+    /// queue with the same capacity and mode (like `Vec::split_off`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len()`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.elements.len();
+        assert!(
+            at <= len,
+            "split index (is {at}) should be <= len (is {len})"
+        );
+        self.invalidate_weight();
+        Self {
+            elements: self.elements.split_off(at),
+            cap: self.cap,
+            overwrite: self.overwrite,
+            weight: self.weight.clone(),
+        }
+    }
+
+    /// Puts the item in the queue if there is room for it and
+    /// returns `true`, otherwise drops the item and returns `false`.
+    ///
+    /// Useful when losing items is acceptable (e.g. for logs): unlike
+    /// `put` there is no error to handle and unlike `try_put` the
+    /// item is not handed back. Even in overwrite mode no element
+    /// is evicted, the new item is the one dropped.
+    pub fn saturating_put(&mut self, item: T) -> bool {
+        self.try_put(item).is_ok()
+    }
+}
+
+impl<T> Default for Fifo<T> {
+    // the default queue is unbounded
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Queue<T> for Fifo<T> {
+    /// Memory is allocated upfront for at most 1024 elements, the
+    /// storage then grows as elements are put so that a huge `cap`
+    /// can be used as a mere bound.
+    fn with_capacity(cap: usize) -> Self {
+        // a capacity of usize::MAX is an unbounded queue (see `new`),
+        // we must not try to allocate room for that many elements
+        if cap == usize::MAX {
+            return Self::new();
+        }
+        Self {
+            // cap may be too big to be allocated, see MAX_PREALLOCATED
+            elements: VecDeque::with_capacity(cap.min(MAX_PREALLOCATED)),
+            cap,
+            overwrite: false,
+            weight: None,
+        }
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.elements.front()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let item = self.elements.pop_front()?;
+        self.remove_weight(&item);
+        Some(item)
+    }
+
+    fn put(&mut self, item: T) -> Result<(), Error> {
+        // an evicted element is simply dropped
+        self.put_overwrite(item).map(|_| ())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    fn is_full(&self) -> bool {
+        let heavy = self
+            .weight
+            .as_ref()
+            .zip(self.total_weight())
+            .is_some_and(|(limit, total)| total >= limit.max);
+        (!self.is_unbounded() && self.elements.len() == self.cap) || heavy
+    }
+}
+
+impl<T> Len for Fifo<T> {
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+}
+
+impl<T: Clone> From<&[T]> for Fifo<T> {
+    // elements are put in slice order, so the first
+    // element of the slice is the first to pop
+    fn from(value: &[T]) -> Self {
+        Self {
+            elements: value.iter().cloned().collect(),
+            cap: value.len(),
+            overwrite: false,
+            weight: None,
+        }
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Fifo<T> {
+    // the array elements are moved, not cloned, and the
+    // first element of the array is the first to pop
+    fn from(value: [T; N]) -> Self {
+        Self {
+            elements: VecDeque::from(value),
+            cap: N,
+            overwrite: false,
+            weight: None,
+        }
+    }
+}
+
+impl<T> TryFrom<Vec<T>> for Fifo<T> {
+    type Error = Error;
+
+    // the capacity is the vector length so this cannot fail,
+    // see `try_from_with_capacity` for a fallible conversion
+    fn try_from(value: Vec<T>) -> Result<Self, Self::Error> {
+        let cap = value.len();
+        Self::try_from_with_capacity(value, cap)
+    }
+}
+
+impl<T> From<Fifo<T>> for Vec<T> {
+    // the returned vector is in pop order
+    fn from(value: Fifo<T>) -> Self {
+        Vec::from(value.elements)
+    }
+}
+
+/// An iterator that owns a [`Fifo`] and pops its elements,
+/// created by the `into_iter` method of `Fifo`.
+pub struct FifoIntoIter<T> {
+    fifo: Fifo<T>,
+}
+
+impl<T> Iterator for FifoIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.fifo.pop()
+    }
+}
+
+impl<T> IntoIterator for Fifo<T> {
+    type Item = T;
+    type IntoIter = FifoIntoIter<T>;
+
+    // elements are yielded in pop order
+    fn into_iter(self) -> Self::IntoIter {
+        FifoIntoIter { fifo: self }
+    }
+}
+
+impl<T> FromIterator<T> for Fifo<T> {
+    // elements are put in iteration order and the capacity
+    // of the queue is the number of collected elements
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let elements: Vec<T> = iter.into_iter().collect();
+        Self {
+            cap: elements.len(),
+            elements: VecDeque::from(elements),
+            overwrite: false,
+            weight: None,
+        }
+    }
+}
+
+impl<T> Extend<T> for Fifo<T> {
+    // elements are put in iteration order. In overwrite mode put
+    // never fails so every element is put, evicting the oldest
+    // ones. Otherwise extending stops at the first element that
+    // doesn't fit: that element is dropped and the rest of the
+    // iterator is left unconsumed
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            if self.put(item).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+impl<T: Debug> Debug for Fifo<T> {
+    // elements are printed in pop order
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Display> Display for Fifo<T> {
+    // elements are printed in pop order, front first, the newest element last,
+    // the arrows showing the direction elements move in
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[")?;
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, " <- ")?;
+            }
+            write!(f, "{item}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<T: PartialEq> PartialEq for Fifo<T> {
+    // two queues are equal if they would pop the same
+    // elements in the same order, whatever their capacity
+    fn eq(&self, other: &Self) -> bool {
+        self.elements == other.elements
+    }
+}
+
+// the equality of the elements is an equivalence
+// relation, so is the equality of the queues
+impl<T: Eq> Eq for Fifo<T> {}
+
+impl<T: Hash> Hash for Fifo<T> {
+    // consistent with PartialEq: only the elements are hashed,
+    // in pop order, so two equal queues have the same hash
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for item in self.iter() {
+            item.hash(state);
+        }
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Fifo<T> {
+    // like Vec, queues are compared lexicographically,
+    // element by element in pop order
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for Fifo<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T> Index<usize> for Fifo<T> {
+    type Output = T;
+
+    // index 0 is the next element to pop
+    fn index(&self, index: usize) -> &Self::Output {
+        let len = self.elements.len();
+        self.elements.get(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+impl<T> IndexMut<usize> for Fifo<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.invalidate_weight();
+        let len = self.elements.len();
+        self.elements.get_mut(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+#[test]
+fn fifo_pops_in_insertion_order() {
+    let mut fifo = Fifo::with_capacity(3);
+    assert!(fifo.is_empty());
+
+    assert!(fifo.put(1).is_ok());
+    assert!(fifo.put(2).is_ok());
+    assert!(fifo.put(3).is_ok());
+    assert!(!fifo.is_empty());
+
+    assert_eq!(fifo.peek(), Some(&1));
+    assert_eq!(fifo.pop(), Some(1));
+    assert_eq!(fifo.peek(), Some(&2));
+    assert_eq!(fifo.pop(), Some(2));
+    assert_eq!(fifo.pop(), Some(3));
+
+    assert_eq!(fifo.peek(), None);
+    assert_eq!(fifo.pop(), None);
+    assert!(fifo.is_empty());
+}
+
+#[test]
+fn fifo_conversions() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+    assert_eq!(Vec::from(fifo), vec![1, 2, 3]);
+
+    let mut fifo = Fifo::from(["a", "b"].as_slice());
+    assert_eq!(fifo.pop(), Some("a"));
+    let v: Vec<&str> = fifo.into();
+    assert_eq!(v, vec!["b"]);
+}
+
+#[test]
+fn fifo_put_on_full_queue() {
+    let mut fifo = Fifo::with_capacity(2);
+    assert_eq!(fifo.put(1), Ok(()));
+    assert_eq!(fifo.put(2), Ok(()));
+    assert_eq!(fifo.put(3), Err(Error::Full));
+
+    // the rejected item never made it in the queue
+    assert_eq!(fifo.pop(), Some(1));
+    assert_eq!(fifo.pop(), Some(2));
+    assert_eq!(fifo.pop(), None);
+}
+
+#[test]
+fn fifo_capacity_is_a_hard_bound() {
+    let mut fifo = Fifo::with_capacity(3);
+    for i in 0..3 {
+        assert!(!fifo.is_full());
+        assert_eq!(fifo.put(i), Ok(()));
+    }
+    assert!(fifo.is_full());
+    assert_eq!(fifo.put(3), Err(Error::Full));
+
+    // popping frees a slot
+    assert_eq!(fifo.pop(), Some(0));
+    assert!(!fifo.is_full());
+    assert_eq!(fifo.put(3), Ok(()));
+    assert!(fifo.is_full());
+}
+
+#[test]
+fn fifo_huge_capacity_is_not_allocated() {
+    let cap = usize::MAX - 1;
+    let mut fifo: Fifo<u8> = Fifo::with_capacity(cap);
+    assert_eq!(fifo.capacity(), cap);
+    assert_eq!(fifo.put(1), Ok(()));
+    assert!(!fifo.is_full());
+
+    // the conversions keep the capacity without allocating it either
+    let lifo = fifo.into_lifo();
+    assert_eq!(lifo.capacity(), cap);
+    assert_eq!(lifo.into_fifo().capacity(), cap);
+}
+
+#[test]
+fn fifo_zero_capacity_is_always_full() {
+    let mut fifo = Fifo::with_capacity(0);
+    assert!(fifo.is_full());
+    assert!(fifo.is_empty());
+    assert_eq!(fifo.put(42), Err(Error::Full));
+    assert_eq!(fifo.pop(), None);
+}
+
+#[test]
+fn fifo_many_elements() {
+    const COUNT: usize = 10_000;
+
+    let mut fifo = Fifo::with_capacity(COUNT);
+    for i in 0..COUNT {
+        assert_eq!(fifo.put(i), Ok(()));
+    }
+    assert!(fifo.is_full());
+
+    for i in 0..COUNT {
+        assert_eq!(fifo.pop(), Some(i));
+    }
+    assert!(fifo.is_empty());
+}
+
+#[test]
+fn fifo_len_and_capacity() {
+    let mut fifo = Fifo::with_capacity(2);
+    assert_eq!(fifo.len(), 0);
+    assert_eq!(fifo.capacity(), 2);
+
+    assert_eq!(fifo.put(1), Ok(()));
+    assert_eq!(fifo.len(), 1);
+    assert_eq!(fifo.put(2), Ok(()));
+    assert_eq!(fifo.len(), 2);
+
+    // a rejected put doesn't change the length
+    assert_eq!(fifo.put(3), Err(Error::Full));
+    assert_eq!(fifo.len(), fifo.capacity());
+
+    assert!(fifo.pop().is_some());
+    assert_eq!(fifo.len(), 1);
+    assert!(fifo.pop().is_some());
+    assert_eq!(fifo.len(), 0);
+    assert!(fifo.pop().is_none());
+    assert_eq!(fifo.len(), 0);
+
+    // the capacity never changes
+    assert_eq!(fifo.capacity(), 2);
+}
+
+#[test]
+fn fifo_into_iter() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+    let mut popped = Fifo::from([1, 2, 3].as_slice());
+
+    let collected: Vec<i32> = fifo.into_iter().collect();
+    let mut expected = vec![];
+    while let Some(i) = popped.pop() {
+        expected.push(i);
+    }
+
+    assert_eq!(collected, expected);
+    assert_eq!(collected, vec![1, 2, 3]);
+
+    // we can use a Fifo directly in a for loop
+    let mut items = vec![];
+    for item in Fifo::from([1, 2, 3].as_slice()) {
+        items.push(item);
+    }
+    assert_eq!(items, vec![1, 2, 3]);
+}
+
+#[test]
+fn fifo_iter() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+
+    let refs: Vec<&i32> = fifo.iter().collect();
+    assert_eq!(refs, vec![&1, &2, &3]);
+
+    // the queue is left intact
+    assert_eq!(fifo.len(), 3);
+    assert_eq!(fifo.peek(), Some(&1));
+}
+
+#[test]
+fn fifo_drain() {
+    let mut fifo = Fifo::from([0, 1, 2, 3, 4, 5, 6, 7, 8, 9].as_slice());
+
+    let all: Vec<i32> = Fifo::from([0, 1, 2].as_slice()).drain().collect();
+    assert_eq!(all, vec![0, 1, 2]);
+
+    // we only consume half of the iterator
+    let half: Vec<i32> = fifo.drain().take(5).collect();
+    assert_eq!(half, vec![0, 1, 2, 3, 4]);
+
+    // the remaining elements have been dropped with the iterator
+    assert!(fifo.is_empty());
+    assert_eq!(fifo.len(), 0);
+    assert_eq!(fifo.pop(), None);
+}
+
+#[test]
+fn fifo_from_iterator() {
+    let mut fifo: Fifo<_> = (0..5).collect();
+    assert_eq!(fifo.len(), 5);
+    assert_eq!(fifo.capacity(), 5);
+    assert!(fifo.is_full());
+    assert_eq!(fifo.drain().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn fifo_extend() {
+    let mut fifo = Fifo::with_capacity(5);
+    fifo.extend(0..3);
+    assert_eq!(fifo.len(), 3);
+
+    // extending past the capacity stops once the queue is full
+    fifo.extend(3..10);
+    assert_eq!(fifo.len(), 5);
+    assert!(fifo.is_full());
+    assert_eq!(fifo.drain().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+
+    // the element that didn't fit is dropped, the
+    // following ones are not consumed
+    let mut fifo = Fifo::with_capacity(2);
+    let mut iter = 0..5;
+    fifo.extend(iter.by_ref());
+    assert_eq!(iter.next(), Some(3));
+}
+
+#[test]
+fn fifo_extend_overwrite() {
+    let mut fifo = Fifo::with_capacity_overwrite(3);
+    fifo.extend(0..10);
+    // every element has been put, the latest ones are kept
+    assert!(fifo.is_full());
+    assert_eq!(fifo.drain().collect::<Vec<_>>(), vec![7, 8, 9]);
+}
+
+#[test]
+fn fifo_try_put() {
+    let mut fifo = Fifo::with_capacity(2);
+    assert_eq!(fifo.try_put(String::from("hello")), Ok(()));
+    assert_eq!(fifo.try_put(String::from("world")), Ok(()));
+
+    // the queue is full, we get the item back
+    let rejected = fifo.try_put(String::from("rejected")).unwrap_err();
+    assert_eq!(rejected, "rejected");
+    assert_eq!(fifo.len(), 2);
+
+    // once a slot is free, we can retry with the same item
+    assert!(fifo.pop().is_some());
+    assert_eq!(fifo.try_put(rejected), Ok(()));
+    assert!(fifo.iter().any(|s| s == "rejected"));
+}
+
+#[test]
+fn fifo_debug() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+    assert_eq!(format!("{fifo:?}"), "[1, 2, 3]");
+}
+
+#[test]
+fn fifo_clone() {
+    let mut fifo = Fifo::from([1, 2, 3].as_slice());
+    let clone = fifo.clone();
+
+    // the clone is independent from the original
+    assert!(fifo.pop().is_some());
+    assert_eq!(fifo.len(), 2);
+    assert_eq!(clone.len(), 3);
+    assert_eq!(clone.capacity(), fifo.capacity());
+}
+
+#[test]
+fn fifo_partial_eq() {
+    let a = Fifo::from([1, 2, 3].as_slice());
+    let mut b = Fifo::with_capacity(10);
+    b.extend([1, 2, 3]);
+
+    // same elements in the same order but different capacities
+    assert_eq!(a, b);
+
+    assert!(b.pop().is_some());
+    assert_ne!(a, b);
+    assert_ne!(a, Fifo::from([3, 2, 1].as_slice()));
+}
+
+#[test]
+fn fifo_peek_mut() {
+    let mut fifo = Fifo::from([1, 2, 3].as_slice());
+
+    if let Some(next) = fifo.peek_mut() {
+        *next *= 10;
+    }
+    assert_eq!(fifo.len(), 3);
+    assert_eq!(fifo.pop(), Some(10));
+
+    let mut empty: Fifo<i32> = Fifo::with_capacity(1);
+    assert_eq!(empty.peek_mut(), None);
+}
+
+#[test]
+fn fifo_clear() {
+    let mut fifo = Fifo::with_capacity(3);
+    fifo.extend(0..3);
+    assert!(fifo.is_full());
+
+    fifo.clear();
+    assert!(fifo.is_empty());
+    assert_eq!(fifo.len(), 0);
+    assert_eq!(fifo.capacity(), 3);
+
+    // the queue can be filled again
+    fifo.extend(3..6);
+    assert!(fifo.is_full());
+    assert_eq!(fifo.pop(), Some(3));
+}
+
+#[test]
+fn fifo_reserve() {
+    let mut fifo = Fifo::with_capacity(2);
+    fifo.extend(0..2);
+    assert!(fifo.is_full());
+
+    assert_eq!(fifo.reserve(3), Ok(()));
+    assert_eq!(fifo.capacity(), 5);
+    assert_eq!(fifo.len(), 2);
+    assert!(!fifo.is_full());
+    assert_eq!(fifo.put(2), Ok(()));
+
+    // there is already room for one more element
+    // so the capacity doesn't change
+    assert_eq!(fifo.reserve(1), Ok(()));
+    assert_eq!(fifo.capacity(), 5);
+}
+
+#[test]
+fn fifo_contains() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+    assert!(fifo.contains(&2));
+    assert!(!fifo.contains(&42));
+
+    let empty: Fifo<i32> = Fifo::with_capacity(1);
+    assert!(!empty.contains(&1));
+}
+
+#[test]
+fn fifo_retain() {
+    let mut fifo: Fifo<i32> = (0..10).collect();
+    fifo.retain(|i| i % 2 == 0);
+
+    assert_eq!(fifo.len(), 5);
+    assert_eq!(fifo.drain().collect::<Vec<_>>(), vec![0, 2, 4, 6, 8]);
+}
+
+#[test]
+fn fifo_overwrite_mode() {
+    let mut fifo = Fifo::with_capacity_overwrite(3);
+    assert_eq!(fifo.put_overwrite(1), Ok(None));
+    assert_eq!(fifo.put_overwrite(2), Ok(None));
+    assert_eq!(fifo.put_overwrite(3), Ok(None));
+    assert!(fifo.is_full());
+
+    // the oldest element is evicted to make room
+    assert_eq!(fifo.put_overwrite(4), Ok(Some(1)));
+    assert_eq!(fifo.len(), 3);
+
+    // put never fails, the evicted element is dropped
+    assert_eq!(fifo.put(5), Ok(()));
+    assert_eq!(fifo.drain().collect::<Vec<_>>(), vec![3, 4, 5]);
+
+    // try_put doesn't evict
+    fifo.extend([6, 7, 8]);
+    assert_eq!(fifo.try_put(9), Err(9));
+
+    // nothing can be stored in a zero capacity queue
+    let mut zero = Fifo::with_capacity_overwrite(0);
+    assert_eq!(zero.put_overwrite(1), Ok(Some(1)));
+    assert!(zero.is_empty());
+}
+
+#[test]
+fn fifo_default_mode_does_not_overwrite() {
+    let mut fifo = Fifo::with_capacity(1);
+    assert_eq!(fifo.put_overwrite(1), Ok(None));
+    assert_eq!(fifo.put_overwrite(2), Err(Error::Full));
+    assert_eq!(fifo.pop(), Some(1));
+}
+
+#[test]
+fn fifo_into_lifo() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+    let mut lifo = fifo.clone().into_lifo();
+    assert_eq!(lifo.capacity(), fifo.capacity());
+
+    for item in fifo {
+        assert_eq!(lifo.pop(), Some(item));
+    }
+    assert!(lifo.is_empty());
+}
+
+#[test]
+fn fifo_append() {
+    let mut a = Fifo::with_capacity(4);
+    a.extend([1, 2]);
+    let mut b = Fifo::from([3, 4].as_slice());
+
+    assert_eq!(a.append(&mut b), Ok(()));
+    assert!(b.is_empty());
+    assert_eq!(a.drain().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn fifo_append_overflow() {
+    let mut a = Fifo::from([1, 2].as_slice());
+    let mut b = Fifo::from([3].as_slice());
+
+    assert_eq!(a.append(&mut b), Err(Error::Full));
+    // nothing has been moved
+    assert_eq!(a.len(), 2);
+    assert_eq!(b.len(), 1);
+}
+
+#[test]
+fn fifo_as_slice() {
+    let mut fifo = Fifo::with_capacity(4);
+    fifo.extend([3, 4]);
+    // make the elements wrap around the end of the buffer
+    assert_eq!(fifo.pop(), Some(3));
+    fifo.extend([1, 2, 5]);
+
+    assert_eq!(fifo.as_slice(), &[4, 1, 2, 5]);
+
+    fifo.as_mut_slice().sort();
+    assert_eq!(fifo.as_slice(), &[1, 2, 4, 5]);
+
+    // pop order matches the slice order
+    let slice = fifo.as_slice().to_vec();
+    assert_eq!(fifo.drain().collect::<Vec<_>>(), slice);
+}
+
+#[test]
+fn fifo_map() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+    let mut strings: Fifo<String> = fifo.map(|i| i.to_string());
+
+    assert_eq!(strings.capacity(), 3);
+    assert_eq!(strings.pop(), Some(String::from("1")));
+    assert_eq!(strings.pop(), Some(String::from("2")));
+    assert_eq!(strings.pop(), Some(String::from("3")));
+}
+
+#[test]
+fn fifo_index() {
+    let mut fifo = Fifo::with_capacity(4);
+    fifo.extend([1, 2, 3]);
+    assert_eq!(fifo[0], 1);
+    assert_eq!(fifo[2], 3);
+
+    fifo[1] = 20;
+    assert_eq!(fifo.pop(), Some(1));
+    // indexes follow the pop order
+    assert_eq!(fifo[0], 20);
+    assert_eq!(fifo.pop(), Some(20));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+fn fifo_index_out_of_bounds() {
+    let fifo = Fifo::from([1, 2].as_slice());
+    let _ = fifo[2];
+}
+
+#[test]
+fn fifo_iter_rev() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+
+    let forward: Vec<&i32> = fifo.iter().collect();
+    let backward: Vec<&i32> = fifo.iter().rev().collect();
+    assert_eq!(forward, vec![&1, &2, &3]);
+    assert_eq!(backward, vec![&3, &2, &1]);
+
+    // both are mirror images
+    assert!(forward.iter().eq(backward.iter().rev()));
+}
+
+#[test]
+fn fifo_filled_with_default() {
+    let mut fifo: Fifo<String> = Fifo::filled_with_default(3);
+    assert_eq!(fifo.len(), 3);
+    assert!(fifo.is_full());
+    assert!(fifo.drain().all(|s| s == String::default()));
+
+    let fifo: Fifo<i32> = Fifo::filled_with_default(0);
+    assert!(fifo.is_empty());
+    assert!(fifo.is_full());
+}
+
+#[test]
+fn fifo_peek_n() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+    assert_eq!(fifo.peek_n(2), vec![&1, &2]);
+    assert_eq!(fifo.peek_n(3), vec![&1, &2, &3]);
+    // n is clamped to the number of elements
+    assert_eq!(fifo.peek_n(10), vec![&1, &2, &3]);
+    assert!(fifo.peek_n(0).is_empty());
+
+    // nothing has been popped
+    assert_eq!(fifo.len(), 3);
+}
+
+#[test]
+fn fifo_pop_n() {
+    let mut fifo = Fifo::from([1, 2, 3, 4, 5].as_slice());
+    assert_eq!(fifo.pop_n(2), vec![1, 2]);
+    assert_eq!(fifo.len(), 3);
+
+    assert!(fifo.pop_n(0).is_empty());
+    assert_eq!(fifo.len(), 3);
+
+    assert_eq!(fifo.pop_n(1), vec![3]);
+    assert_eq!(fifo.len(), 2);
+
+    // asking for more than available pops everything
+    assert_eq!(fifo.pop_n(10), vec![4, 5]);
+    assert!(fifo.is_empty());
+    assert!(fifo.pop_n(1).is_empty());
+}
+
+#[test]
+fn fifo_new_is_unbounded() {
+    let mut fifo = Fifo::new();
+    assert!(fifo.is_empty());
+    assert!(!fifo.is_full());
+    assert_eq!(fifo.capacity(), usize::MAX);
+
+    // other conversions keep the queue unbounded
+    let other = fifo.clone().into_lifo();
+    assert_eq!(other.capacity(), usize::MAX);
+
+    fifo.extend(0..100);
+    assert_eq!(fifo.len(), 100);
+    assert_eq!(fifo.pop(), Some(0));
+}
+
+#[test]
+fn fifo_hash() {
+    use std::collections::HashSet;
+
+    let mut a = Fifo::with_capacity(10);
+    a.extend([1, 2, 3]);
+    let b = Fifo::from([1, 2, 3].as_slice());
+    let c = Fifo::from([3, 2, 1].as_slice());
+
+    let mut set = HashSet::new();
+    assert!(set.insert(a));
+    // b is equal to a even though their capacities differ
+    assert!(!set.insert(b));
+    assert!(set.insert(c));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn fifo_shrink_to_fit() {
+    let mut fifo = Fifo::with_capacity(4);
+    fifo.extend([1, 2]);
+    assert_eq!(fifo.reserve(100), Ok(()));
+    assert!(fifo.spare_capacity() >= 100);
+
+    fifo.shrink_to_fit();
+    assert!(fifo.spare_capacity() < 100);
+    assert_eq!(fifo.len(), 2);
+    // the logical bound is unchanged
+    assert_eq!(fifo.capacity(), 102);
+    assert_eq!(fifo.put(3), Ok(()));
+}
+
+#[test]
+fn fifo_rotate_left() {
+    // pops 1, 2, 3, 4
+    let mut fifo = Fifo::from([1, 2, 3, 4].as_slice());
+
+    fifo.rotate_left(1);
+    assert_eq!(fifo.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 1]);
+
+    // rotating by len is a no-op
+    fifo.rotate_left(4);
+    assert_eq!(fifo.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 1]);
+
+    // mid is taken modulo len
+    fifo.rotate_left(7);
+    assert_eq!(fifo.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+    let mut empty: Fifo<i32> = Fifo::new();
+    empty.rotate_left(3);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn fifo_dedup_consecutive() {
+    let mut fifo = Fifo::from([1, 1, 2, 3, 3, 3, 2].as_slice());
+    fifo.dedup_consecutive();
+    assert_eq!(fifo.drain().collect::<Vec<_>>(), [1, 2, 3, 2]);
+    assert_eq!(fifo.capacity(), 7);
+
+    let mut distinct = Fifo::from([1, 2, 3].as_slice());
+    distinct.dedup_consecutive();
+    assert_eq!(distinct, Fifo::from([1, 2, 3].as_slice()));
+
+    let mut empty: Fifo<i32> = Fifo::new();
+    empty.dedup_consecutive();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn fifo_min_max() {
+    let fifo = Fifo::from([3, 1, 4, 1, 5].as_slice());
+    assert_eq!(fifo.min_element(), Some(&1));
+    assert_eq!(fifo.max_element(), Some(&5));
+    // the queue is not consumed
+    assert_eq!(fifo.len(), 5);
+
+    let words: Fifo<String> = ["pear", "apple", "fig"]
+        .map(String::from)
+        .into_iter()
+        .collect();
+    assert_eq!(words.min_element().map(String::as_str), Some("apple"));
+    assert_eq!(words.max_element().map(String::as_str), Some("pear"));
+
+    let empty: Fifo<i32> = Fifo::new();
+    assert_eq!(empty.min_element(), None);
+    assert_eq!(empty.max_element(), None);
+}
+
+#[test]
+fn fifo_try_from_vec() {
+    let mut fifo = Fifo::try_from(vec![1, 2, 3]).unwrap();
+    assert_eq!(fifo.capacity(), 3);
+    assert!(fifo.is_full());
+    assert_eq!(fifo.drain().collect::<Vec<_>>(), [1, 2, 3]);
+
+    let mut fifo = Fifo::try_from_with_capacity(vec![1, 2], 4).unwrap();
+    assert_eq!(fifo.capacity(), 4);
+    assert_eq!(fifo.put(3), Ok(()));
+    assert_eq!(fifo.pop(), Some(1));
+
+    assert_eq!(
+        Fifo::try_from_with_capacity(vec![1, 2, 3], 2).unwrap_err(),
+        Error::Capacity(3)
+    );
+}
+
+#[test]
+fn fifo_reserve_overflow() {
+    let mut fifo = Fifo::from([1].as_slice());
+    // 1 + usize::MAX overflows
+    assert_eq!(fifo.reserve(usize::MAX), Err(Error::Capacity(usize::MAX)));
+    // no overflow but that much memory cannot be allocated
+    let mut empty: Fifo<u64> = Fifo::with_capacity(0);
+    assert_eq!(empty.reserve(usize::MAX), Err(Error::Capacity(usize::MAX)));
+    assert_eq!(empty.capacity(), 0);
+
+    // the queue is unchanged
+    assert_eq!(fifo.capacity(), 1);
+    assert_eq!(fifo.reserve(2), Ok(()));
+    assert_eq!(fifo.capacity(), 3);
+}
+
+#[test]
+fn fifo_from_array() {
+    let mut fifo = Fifo::from([1, 2, 3]);
+    assert_eq!(fifo.capacity(), 3);
+    assert!(fifo.is_full());
+    assert_eq!(fifo.pop(), Some(1));
+
+    // no Clone bound: the elements are moved into the queue
+    struct NotClone(u8);
+    let mut fifo = Fifo::from([NotClone(1), NotClone(2), NotClone(3)]);
+    assert_eq!(fifo.pop().map(|n| n.0), Some(1));
+
+    let empty: Fifo<NotClone> = Fifo::from([]);
+    assert!(empty.is_full());
+}
+
+#[test]
+fn fifo_to_vec_pop_order() {
+    let fifo = Fifo::from([1, 2, 3]);
+    let snapshot = fifo.to_vec_pop_order();
+
+    let mut popped = Vec::new();
+    let mut clone = fifo.clone();
+    while let Some(&front) = clone.peek() {
+        assert_eq!(clone.pop(), Some(front));
+        popped.push(front);
+    }
+    assert_eq!(snapshot, popped);
+
+    // the original queue is unchanged
+    assert_eq!(fifo, Fifo::from([1, 2, 3]));
+    assert!(Fifo::<i32>::new().to_vec_pop_order().is_empty());
+}
+
+#[test]
+fn fifo_fold() {
+    let fifo = Fifo::from([1, 2, 3, 4]);
+    assert_eq!(fifo.fold(0, |acc, x| acc + x), 10);
+    // the elements are folded in pop order
+    assert_eq!(fifo.fold(0, |acc, x| acc * 10 + x), 1234);
+    assert_eq!(fifo.len(), 4);
+
+    assert_eq!(Fifo::<i32>::new().fold(42, |acc, x| acc + x), 42);
+}
+
+#[test]
+fn fifo_filter_into() {
+    let fifo: Fifo<i32> = (0..6).collect();
+    let mut odds = fifo.filter_into(|x| x % 2 == 1);
+    assert_eq!(odds.capacity(), 3);
+    assert!(odds.is_full());
+    assert_eq!(odds.drain().collect::<Vec<_>>(), [1, 3, 5]);
+
+    let none = Fifo::from([1, 2, 3]).filter_into(|_| false);
+    assert!(none.is_empty());
+    assert_eq!(none.capacity(), 0);
+}
+
+#[test]
+fn fifo_weight_limit() {
+    let mut fifo = Fifo::with_weight_limit(10, |s: &String| s.len());
+    assert_eq!(fifo.total_weight(), Some(0));
+    assert_eq!(fifo.put("hello".to_string()), Ok(()));
+    assert_eq!(fifo.put("abcd".to_string()), Ok(()));
+    // just under the limit
+    assert_eq!(fifo.total_weight(), Some(9));
+    assert!(!fifo.is_full());
+
+    // 2 more bytes would go over the limit
+    assert_eq!(fifo.put("ab".to_string()), Err(Error::Full));
+    assert_eq!(fifo.try_put("ab".to_string()), Err("ab".to_string()));
+    assert_eq!(fifo.len(), 2);
+
+    // but 1 more byte fits exactly
+    assert_eq!(fifo.put("a".to_string()), Ok(()));
+    assert_eq!(fifo.total_weight(), Some(10));
+    assert!(fifo.is_full());
+    assert_eq!(fifo.put(String::new()), Err(Error::Full));
+
+    // popping frees some weight
+    assert_eq!(fifo.pop().as_deref(), Some("hello"));
+    assert_eq!(fifo.put("ab".to_string()), Ok(()));
+    assert_eq!(fifo.capacity(), usize::MAX);
+
+    let mut other = Fifo::from(["12345".to_string()]);
+    assert_eq!(fifo.append(&mut other), Err(Error::Full));
+    assert_eq!(other.len(), 1);
+
+    assert_eq!(Fifo::<String>::new().total_weight(), None);
+}
+
+#[test]
+fn fifo_ordering() {
+    // a Fifo popping the items in the given order
+    let pops = |items: &[i32]| items.iter().copied().collect::<Fifo<_>>();
+    assert!(pops(&[1, 2]) < pops(&[1, 3]));
+    // a prefix is smaller
+    assert!(pops(&[1]) < pops(&[1, 2]));
+    assert!(pops(&[]) < pops(&[0]));
+    assert!(pops(&[2]) > pops(&[1, 5]));
+    // Ord::min and Ord::max compare whole queues (see min_element)
+    assert_eq!(pops(&[1, 2]).min(pops(&[0])), pops(&[0]));
+    assert_eq!(pops(&[1, 2]).max(pops(&[0])), pops(&[1, 2]));
+
+    let mut queues = vec![pops(&[2]), pops(&[1, 3]), pops(&[1]), pops(&[1, 2])];
+    queues.sort();
+    assert_eq!(
+        queues,
+        [pops(&[1]), pops(&[1, 2]), pops(&[1, 3]), pops(&[2])]
+    );
+}
+
+#[test]
+fn fifo_split_off() {
+    let mut fifo = Fifo::from([1, 2, 3, 4]);
+    let mut rest = fifo.split_off(2);
+    assert_eq!(rest.capacity(), 4);
+    assert_eq!(fifo.drain().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(rest.drain().collect::<Vec<_>>(), [3, 4]);
+
+    let mut fifo = Fifo::from([1, 2]);
+    assert!(fifo.split_off(2).is_empty());
+    assert_eq!(fifo.split_off(0), Fifo::from([1, 2]));
+    assert!(fifo.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "split index (is 3) should be <= len (is 2)")]
+fn fifo_split_off_out_of_range() {
+    Fifo::from([1, 2]).split_off(3);
+}
+
+#[test]
+fn fifo_saturating_put() {
+    let mut fifo = Fifo::with_capacity(2);
+    assert!(fifo.saturating_put(1));
+    assert!(fifo.saturating_put(2));
+    assert!(!fifo.saturating_put(3));
+    assert_eq!(fifo.len(), 2);
+    assert!(!fifo.contains(&3));
+}
+
+#[test]
+fn fifo_saturating_put_does_not_overwrite() {
+    let mut fifo = Fifo::with_capacity_overwrite(1);
+    assert!(fifo.saturating_put(1));
+    assert!(!fifo.saturating_put(2));
+    assert_eq!(fifo.pop(), Some(1));
+}
+
+#[test]
+fn fifo_display() {
+    let fifo = Fifo::from([1, 2, 3]);
+    assert_eq!(fifo.to_string(), "[1 <- 2 <- 3]");
+    // distinct from Debug
+    assert_eq!(format!("{fifo:?}"), "[1, 2, 3]");
+
+    assert_eq!(Fifo::from([1]).to_string(), "[1]");
+    assert_eq!(Fifo::<i32>::new().to_string(), "[]");
+}
+
+#[test]
+fn fifo_weight_running_total() {
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let mut fifo = Fifo::with_weight_limit(1000, move |s: &String| {
+        counter.fetch_add(1, Relaxed);
+        s.len()
+    });
+    for _ in 0..100 {
+        assert_eq!(fifo.put("ab".to_string()), Ok(()));
+    }
+    // each put only weighs the new item, and each pop the popped one
+    assert_eq!(calls.load(Relaxed), 100);
+    assert_eq!(fifo.pop().as_deref(), Some("ab"));
+    assert_eq!(calls.load(Relaxed), 101);
+    assert_eq!(fifo.total_weight(), Some(198));
+
+    // elements modified in place are weighed again
+    fifo.peek_mut().unwrap().push_str("cdef");
+    assert_eq!(fifo.total_weight(), Some(202));
+    fifo[1].clear();
+    assert_eq!(fifo.total_weight(), Some(200));
+    fifo.as_mut_slice()[0].clear();
+    assert_eq!(fifo.total_weight(), Some(194));
+
+    fifo.clear();
+    assert_eq!(fifo.total_weight(), Some(0));
+    assert_eq!(fifo.put("x".repeat(1000)), Ok(()));
+    assert!(fifo.is_full());
+}
@@ -4,19 +4,292 @@
 //!    - follow the trait implemented below (you can add methods if you like)
 //!    - implement conversion from &[T]
 //!    - implement conversion into Vec<T>
+//!
+//! # `no_std` support
+//!
+//! The crate only needs the standard library for the `std` feature
+//! (enabled by default). Without it, the crate is `no_std` and only
+//! relies on the `alloc` crate:
+//!    - `Fifo`, `Lifo` and `Deque` store their elements in heap
+//!      allocated collections (`VecDeque` and `Vec`) from `alloc`
+//!    - `ArrayFifo` stores its elements in a fixed size array and
+//!      never allocates
+//!    - `CircularBuffer` allocates a `Vec` of slots from `alloc`
+//!      once, when it is created
+//!    - `SyncQueue` (it needs a `Mutex`) and the `std::error::Error`
+//!      implementation of [`Error`] are only available with `std`
+//!
+//! Tests need `std`, the `no_std` build can be checked with:
+//! `cargo build --no-default-features`
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
-enum Error {}
+mod array_fifo;
+mod circular_buffer;
+mod deque;
+mod fifo;
+mod lifo;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "std")]
+mod sync_queue;
 
-trait Queue<T> {
+pub use array_fifo::ArrayFifo;
+pub use circular_buffer::CircularBuffer;
+pub use deque::Deque;
+pub use fifo::{Fifo, FifoIntoIter};
+pub use lifo::{Lifo, LifoIntoIter};
+#[cfg(feature = "std")]
+pub use sync_queue::SyncQueue;
+
+use core::fmt::Display;
+
+/// Errors returned by the queue operations.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Error {
+    /// The queue already holds as many elements as its capacity allows.
+    Full,
+    /// An operation needs a capacity the queue cannot provide,
+    /// the variant carries the capacity that was attempted.
+    Capacity(usize),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Full => write!(f, "queue is full"),
+            Error::Capacity(cap) => write!(f, "cannot provide a capacity of {cap}"),
+        }
+    }
+}
+
+// Debug and Display are all we need to be a std Error,
+// this lets our Error be used as a Box<dyn std::error::Error>
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+// the maximum number of elements `with_capacity` allocates room
+// for: a capacity is a logical bound, which may be huge, not an
+// amount of memory to allocate upfront
+pub(crate) const MAX_PREALLOCATED: usize = 1024;
+
+pub trait Queue<T> {
+    /// Creates an empty queue able to hold at most `cap` elements.
+    ///
+    /// The capacity is a hard bound: once the queue holds `cap`
+    /// elements, `put` fails until an element is popped. A queue
+    /// with a capacity of `0` is therefore always full.
     fn with_capacity(cap: usize) -> Self;
     // this function returns a reference to the next element to pop
     fn peek(&self) -> Option<&T>;
     // this function must pop the next item according
     // to the kind of queue it is
     fn pop(&mut self) -> Option<T>;
-    // this function must put the item in the queue
-    // Error has to be defined
+    /// Puts the item in the queue.
+    ///
+    /// Returns [`Error::Full`] if the queue is already full, in
+    /// which case the item is dropped and the queue left unchanged.
     fn put(&mut self, item: T) -> Result<(), Error>;
     fn is_empty(&self) -> bool;
+    /// Returns `true` exactly when the queue holds as many
+    /// elements as its capacity.
+    ///
+    /// An unbounded queue (see `Fifo::new`) is never full: its
+    /// length is not compared to its capacity of `usize::MAX`
+    /// elements, which cannot be reached in practice anyway.
     fn is_full(&self) -> bool;
+
+    /// Returns a reference to the next element to pop, or
+    /// `default` if the queue is empty (like `Option::unwrap_or`).
+    ///
+    /// Both references share the lifetime `'a` as the returned
+    /// reference may be either of them.
+    fn peek_or<'a>(&'a self, default: &'a T) -> &'a T {
+        self.peek().unwrap_or(default)
+    }
+
+    /// Puts the items in the queue, in iteration order, until
+    /// there are no more items or `put` fails.
+    ///
+    /// Returns the number of items put, or on failure the number
+    /// of items put before the failure along with the error. The
+    /// item that failed is dropped and the remaining items are
+    /// not consumed.
+    fn put_all<I: IntoIterator<Item = T>>(&mut self, items: I) -> Result<usize, (usize, Error)>
+    where
+        Self: Sized,
+    {
+        let mut count = 0;
+        for item in items {
+            self.put(item).map_err(|e| (count, e))?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Queues able to tell how many elements they hold.
+///
+/// Implementing [`Queue`] doesn't require it: generic code that
+/// needs the length of a queue, like [`transfer`], asks for
+/// `Queue<T> + Len`.
+// is_empty is provided by the Queue trait
+#[allow(clippy::len_without_is_empty)]
+pub trait Len {
+    /// Returns the number of elements in the queue.
+    fn len(&self) -> usize;
+}
+
+/// Moves all the elements of `src` into a new queue of another kind.
+///
+/// The new queue has a capacity of `src.len()` and the elements
+/// are put in the order they are popped from `src`. Depending on
+/// the kinds of queue this keeps or reverses the pop order: a
+/// `Fifo` transferred into a `Lifo` pops its elements in reverse.
+pub fn transfer<T, Q1: Queue<T> + Len, Q2: Queue<T>>(mut src: Q1) -> Q2 {
+    let mut dst = Q2::with_capacity(src.len());
+    while let Some(item) = src.pop() {
+        // dst has room for every element of src so put cannot fail
+        let _ = dst.put(item);
+    }
+    dst
+}
+
+/// Returns `true` if `fifo` and `lifo` would pop the same elements
+/// in the same order.
+///
+/// Only the elements are compared, not the capacities. `PartialEq`
+/// can only compare queues of the same kind, this compares a `Fifo`
+/// and a `Lifo` the way `==` compares two `Fifo`s.
+pub fn same_contents<T: PartialEq>(fifo: &Fifo<T>, lifo: &Lifo<T>) -> bool {
+    fifo.len() == lifo.len() && fifo.iter().eq(lifo.iter())
+}
+
+#[test]
+fn error_display() {
+    assert_eq!(Error::Full.to_string(), "queue is full");
+    assert_eq!(
+        Error::Capacity(42).to_string(),
+        "cannot provide a capacity of 42"
+    );
+}
+
+#[test]
+fn error_can_be_boxed() {
+    fn fill(q: &mut Fifo<i32>) -> Result<(), Box<dyn std::error::Error>> {
+        for i in 0..3 {
+            // `?` converts our Error into a Box<dyn Error>
+            q.put(i)?;
+        }
+        Ok(())
+    }
+
+    let mut fifo = Fifo::with_capacity(2);
+    let err = fill(&mut fifo).unwrap_err();
+    assert_eq!(err.to_string(), "queue is full");
+}
+
+#[test]
+fn transfer_between_queues() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+
+    let mut lifo: Lifo<i32> = transfer(fifo.clone());
+    assert_eq!(lifo.capacity(), 3);
+    assert!(lifo.is_full());
+    assert_eq!(lifo.peek(), Some(&3));
+
+    // and back: the Lifo pops 3, 2, 1 so does the new Fifo
+    let mut back: Fifo<i32> = transfer(lifo.clone());
+    assert_eq!(back.drain().collect::<Vec<_>>(), [3, 2, 1]);
+    assert_eq!(lifo.drain().collect::<Vec<_>>(), [3, 2, 1]);
+
+    // transferring between queues of the same kind keeps the pop order
+    let mut same: Fifo<i32> = transfer(fifo);
+    assert_eq!(same.drain().collect::<Vec<_>>(), [1, 2, 3]);
+
+    let empty: Lifo<i32> = transfer(Fifo::<i32>::with_capacity(5));
+    assert!(empty.is_empty());
+    assert_eq!(empty.capacity(), 0);
+}
+
+#[test]
+fn put_all_unbounded() {
+    let mut fifo = Fifo::new();
+    assert_eq!(fifo.put_all(0..1000), Ok(1000));
+    assert_eq!(fifo.len(), 1000);
+    assert!(!fifo.is_full());
+
+    let mut lifo = Lifo::default();
+    assert_eq!(lifo.put_all(["a", "b"]), Ok(2));
+    assert_eq!(lifo.put_all([]), Ok(0));
+    assert_eq!(lifo.pop(), Some("b"));
+}
+
+#[test]
+fn put_all_bounded() {
+    let mut fifo = Fifo::with_capacity(3);
+    assert_eq!(fifo.put(0), Ok(()));
+    // only 2 items fit before the queue is full
+    assert_eq!(fifo.put_all(1..10), Err((2, Error::Full)));
+    assert_eq!(fifo.drain().collect::<Vec<_>>(), [0, 1, 2]);
+
+    let mut array: ArrayFifo<i32, 2> = ArrayFifo::with_capacity(2);
+    assert_eq!(array.put_all([1, 2, 3]), Err((2, Error::Full)));
+
+    // in overwrite mode put never fails
+    let mut overwrite = Fifo::with_capacity_overwrite(2);
+    assert_eq!(overwrite.put_all(0..5), Ok(5));
+    assert_eq!(overwrite.drain().collect::<Vec<_>>(), [3, 4]);
+}
+
+#[test]
+fn same_contents_across_queue_kinds() {
+    let fifo = Fifo::from([1, 2, 3].as_slice());
+    let lifo = Lifo::from([3, 2, 1].as_slice());
+    assert!(same_contents(&fifo, &lifo));
+
+    // the capacity is not compared
+    let mut bigger = Lifo::with_capacity(10);
+    bigger.extend([3, 2, 1]);
+    assert!(same_contents(&fifo, &bigger));
+
+    // same elements, different pop order
+    let reversed = Lifo::from([1, 2, 3].as_slice());
+    assert!(!same_contents(&fifo, &reversed));
+
+    let shorter = Lifo::from([2, 1].as_slice());
+    assert!(!same_contents(&fifo, &shorter));
+
+    assert!(same_contents(&Fifo::<i32>::new(), &Lifo::new()));
+}
+
+#[test]
+fn peek_or_default() {
+    let fifo = Fifo::from([1, 2]);
+    assert_eq!(fifo.peek_or(&0), &1);
+    let lifo = Lifo::from([1, 2]);
+    assert_eq!(lifo.peek_or(&0), &2);
+
+    let empty: Fifo<String> = Fifo::new();
+    let default = String::from("nothing");
+    assert_eq!(empty.peek_or(&default), "nothing");
+}
+
+#[test]
+fn unbounded_queues_are_never_full() {
+    let mut fifo = Fifo::new();
+    let mut lifo = Lifo::new();
+    for i in 0..100_000 {
+        assert_eq!(fifo.put(i), Ok(()));
+        assert_eq!(lifo.put(i), Ok(()));
+    }
+    assert!(!fifo.is_full());
+    assert!(!lifo.is_full());
+
+    // with_capacity(usize::MAX) is the same as new
+    let mut fifo = Fifo::with_capacity(usize::MAX);
+    assert_eq!(fifo.put_all(0..1000), Ok(1000));
+    assert!(!fifo.is_full());
 }
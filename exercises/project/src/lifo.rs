@@ -0,0 +1,1302 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::ops::{Index, IndexMut};
+
+use crate::{Error, Fifo, Len, MAX_PREALLOCATED, Queue};
+
+/// A last in first out queue (a.k.a. a stack): the
+/// last element put is the first one to be popped.
+#[derive(Clone)]
+pub struct Lifo<T> {
+    // the top of the stack is the end of the vector
+    elements: Vec<T>,
+    // maximum number of elements the queue can hold
+    cap: usize,
+}
+
+// is_empty is provided by the Queue trait
+#[allow(clippy::len_without_is_empty)]
+impl<T> Lifo<T> {
+    /// Creates an empty queue without capacity bound.
+    ///
+    /// Its capacity is `usize::MAX`, a number of elements that cannot
+    /// fit in memory: the queue is never full and `put` never fails.
+    /// Nothing is allocated upfront, `with_capacity(usize::MAX)` is
+    /// the same.
+    pub fn new() -> Self {
+        Self {
+            elements: Vec::new(),
+            cap: usize::MAX,
+        }
+    }
+
+    /// Creates a full queue of capacity `cap` holding `cap`
+    /// default values of `T`.
+    pub fn filled_with_default(cap: usize) -> Self
+    where
+        T: Default,
+    {
+        Self {
+            elements: core::iter::repeat_with(T::default).take(cap).collect(),
+            cap,
+        }
+    }
+
+    /// Creates a queue of capacity `cap` holding the elements of
+    /// `vec` put in vector order, so the last element of the
+    /// vector is the first to pop (like `From<&[T]>`).
+    ///
+    /// Returns [`Error::Capacity`] with the length of `vec` if it
+    /// has more than `cap` elements.
+    pub fn try_from_with_capacity(vec: Vec<T>, cap: usize) -> Result<Self, Error> {
+        if vec.len() > cap {
+            return Err(Error::Capacity(vec.len()));
+        }
+        Ok(Self { elements: vec, cap })
+    }
+
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns the maximum number of elements the queue can hold.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Returns an iterator over references to the elements,
+    /// in the order they would be popped.
+    ///
+    /// The top of the stack is at the end of the backing
+    /// vector, so this walks the vector in reverse.
+    ///
+    /// The iterator is double ended, so `iter().rev()` walks
+    /// the elements in reverse pop order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.elements.iter().rev()
+    }
+
+    /// Removes all the elements from the queue and returns them
+    /// as an iterator, in the order they would be popped.
+    ///
+    /// The queue is empty once the iterator is dropped, even
+    /// if it has not been fully consumed.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.elements.drain(..).rev()
+    }
+
+    /// Puts the item in the queue, like `put`, but hands the
+    /// item back in `Err` if the queue is full so that the
+    /// caller keeps ownership of it.
+    pub fn try_put(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(item);
+        }
+        self.elements.push(item);
+        Ok(())
+    }
+
+    /// Returns a mutable reference to the next element to pop
+    /// (the top of the queue), like `peek` but allowing to
+    /// modify the element without removing it.
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.elements.last_mut()
+    }
+
+    /// Removes all the elements from the queue, its
+    /// capacity is left unchanged.
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    /// Makes room for at least `additional` more elements: the
+    /// capacity is raised to `len() + additional` if it is lower
+    /// and the memory for those elements is allocated upfront.
+    ///
+    /// Returns [`Error::Capacity`] if `len() + additional` overflows
+    /// a `usize` or if the memory cannot be allocated, in which case
+    /// the queue is left unchanged.
+    pub fn reserve(&mut self, additional: usize) -> Result<(), Error> {
+        // a plain addition would panic in debug and silently
+        // wrap around in release, giving a too small capacity
+        let required = self
+            .elements
+            .len()
+            .checked_add(additional)
+            .ok_or(Error::Capacity(usize::MAX))?;
+        self.elements
+            .try_reserve(additional)
+            .map_err(|_| Error::Capacity(required))?;
+        self.cap = self.cap.max(required);
+        Ok(())
+    }
+
+    /// Returns `true` if the queue contains an element equal to `item`.
+    pub fn contains(&self, item: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.elements.contains(item)
+    }
+
+    /// Keeps only the elements for which `f` returns `true`,
+    /// the order of the remaining elements is preserved.
+    ///
+    /// NB: the elements are visited from the bottom of the
+    /// stack to its top, i.e. in reverse pop order.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.elements.retain(f);
+    }
+
+    /// Converts the queue into a [`Fifo`] with the same capacity
+    /// and popping the remaining elements in the same order.
+    pub fn into_fifo(self) -> Fifo<T> {
+        let mut fifo = Fifo::with_capacity(self.cap);
+        // the top of the stack is the next element to pop
+        // so it has to be put first
+        fifo.extend(self.elements.into_iter().rev());
+        fifo
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The elements of `other` are stacked on top of the ones
+    /// already in `self`, so they are popped first.
+    ///
+    /// Returns [`Error::Full`] if `self` cannot hold all the elements,
+    /// in which case neither queue is modified.
+    pub fn append(&mut self, other: &mut Self) -> Result<(), Error> {
+        match self.elements.len().checked_add(other.elements.len()) {
+            Some(len) if len <= self.cap => {}
+            // an overflowing length cannot fit either
+            _ => return Err(Error::Full),
+        }
+        self.elements.append(&mut other.elements);
+        Ok(())
+    }
+
+    /// Returns the elements as a slice, from the bottom of the
+    /// stack to its top.
+    ///
+    /// NB: unlike `Fifo::as_slice` the slice is in REVERSE pop
+    /// order: the top of the stack, the next element to pop, is
+    /// the last element of the slice. The elements are stored
+    /// that way in the `Vec` and a slice cannot be reversed
+    /// without copying it.
+    pub fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns the elements as a mutable slice, in reverse pop
+    /// order (see `as_slice`).
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.elements
+    }
+
+    /// Consumes the queue and returns a queue of the same capacity
+    /// holding the result of `f` applied to each element, the pop
+    /// order is preserved.
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Lifo<U> {
+        Lifo {
+            elements: self.elements.into_iter().map(f).collect(),
+            cap: self.cap,
+        }
+    }
+
+    /// Returns references to the next `n` elements to pop, in pop
+    /// order, or to all the elements if there are less than `n`.
+    pub fn peek_n(&self, n: usize) -> Vec<&T> {
+        self.iter().take(n).collect()
+    }
+
+    /// Pops up to `n` elements and returns them in pop order,
+    /// stopping early if the queue gets empty.
+    pub fn pop_n(&mut self, n: usize) -> Vec<T> {
+        // the next elements to pop are at the end of the vector
+        let at = self.elements.len().saturating_sub(n);
+        self.elements.drain(at..).rev().collect()
+    }
+
+    /// Releases the memory allocated for elements beyond `len()`.
+    ///
+    /// NB: this is about allocation only, the logical bound
+    /// returned by `capacity()` is left unchanged.
+    pub fn shrink_to_fit(&mut self) {
+        self.elements.shrink_to_fit();
+    }
+
+    /// Returns how many more elements fit in the memory already
+    /// allocated, i.e. before putting an element reallocates.
+    ///
+    /// This is distinct from `capacity() - len()`, the number of
+    /// elements the queue accepts before being full: memory is
+    /// allocated as elements are put.
+    pub fn spare_capacity(&self) -> usize {
+        self.elements.capacity() - self.elements.len()
+    }
+
+    /// Rotates the queue so that the element at position `mid`
+    /// in pop order becomes the next one to pop, the elements
+    /// before it being moved, in order, after the last one.
+    ///
+    /// `mid` is taken modulo `len()`, so rotating by `len()`
+    /// leaves the queue unchanged.
+    pub fn rotate_left(&mut self, mid: usize) {
+        let len = self.elements.len();
+        if len == 0 {
+            return;
+        }
+        // pop order is the reverse of the vector order, so rotating
+        // the pop order left rotates the vector right
+        self.elements.rotate_right(mid % len);
+    }
+
+    /// Removes consecutive equal elements, in pop order, like
+    /// `Vec::dedup`.
+    pub fn dedup_consecutive(&mut self)
+    where
+        T: PartialEq,
+    {
+        // runs of equal elements are the same in the
+        // vector order and in the reverse (pop) order
+        self.elements.dedup();
+    }
+
+    /// Returns the smallest element of the queue, or `None` if it is empty.
+    ///
+    /// NB: it is not named `min` as queues of `Ord` elements are
+    /// `Ord` themselves, which already gives them `Ord::min` to get
+    /// the smallest of two queues.
+    pub fn min_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().min()
+    }
+
+    /// Returns the largest element of the queue, or `None` if it is empty.
+    ///
+    /// NB: not named `max` to not clash with `Ord::max`, see `min_element`.
+    pub fn max_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().max()
+    }
+
+    /// Returns a copy of the elements in pop order, without
+    /// modifying the queue (unlike `Vec::from` which consumes it).
+    pub fn to_vec_pop_order(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Folds the elements, in pop order, into an accumulator
+    /// without consuming the queue (see `Iterator::fold`).
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    /// Consumes the queue and returns a queue holding only the
+    /// elements for which `pred` returns `true`, in the same order.
+    ///
+    /// This is the owning counterpart of `retain`, the capacity of
+    /// the returned queue is the number of elements kept.
+    ///
+    /// NB: like `retain`, `pred` is called in reverse pop order.
+    pub fn filter_into<F: FnMut(&T) -> bool>(mut self, pred: F) -> Self {
+        self.retain(pred);
+        self.cap = self.elements.len();
+        self
+    }
+
+    /// Splits the queue in two: `self` keeps the first `at` elements,
+    /// in pop order, and the remaining ones are returned in a new
+    /// queue with the same capacity (like `Vec::split_off`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len()`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.elements.len();
+        assert!(
+            at <= len,
+            "split index (is {at}) should be <= len (is {len})"
+        );
+        // the first elements to pop are at the end of the vector:
+        // self keeps the top of the stack and the bottom is returned
+        let top = self.elements.split_off(len - at);
+        Self {
+            elements: core::mem::replace(&mut self.elements, top),
+            cap: self.cap,
+        }
+    }
+
+    /// Puts the item in the queue if there is room for it and
+    /// returns `true`, otherwise drops the item and returns `false`.
+    ///
+    /// Useful when losing items is acceptable (e.g. for logs): unlike
+    /// `put` there is no error to handle and unlike `try_put` the
+    /// item is not handed back.
+    pub fn saturating_put(&mut self, item: T) -> bool {
+        self.try_put(item).is_ok()
+    }
+}
+
+impl<T> Default for Lifo<T> {
+    // the default queue is unbounded
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Queue<T> for Lifo<T> {
+    /// Memory is allocated upfront for at most 1024 elements, the
+    /// storage then grows as elements are put so that a huge `cap`
+    /// can be used as a mere bound.
+    fn with_capacity(cap: usize) -> Self {
+        // a capacity of usize::MAX is an unbounded queue (see `new`),
+        // we must not try to allocate room for that many elements
+        if cap == usize::MAX {
+            return Self::new();
+        }
+        Self {
+            // cap may be too big to be allocated, see MAX_PREALLOCATED
+            elements: Vec::with_capacity(cap.min(MAX_PREALLOCATED)),
+            cap,
+        }
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.elements.last()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.elements.pop()
+    }
+
+    fn put(&mut self, item: T) -> Result<(), Error> {
+        if self.is_full() {
+            return Err(Error::Full);
+        }
+        self.elements.push(item);
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    fn is_full(&self) -> bool {
+        !self.is_unbounded() && self.elements.len() == self.cap
+    }
+}
+
+impl<T> Len for Lifo<T> {
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+}
+
+impl<T: Clone> From<&[T]> for Lifo<T> {
+    // elements are put in slice order, so the last
+    // element of the slice is the first to pop
+    fn from(value: &[T]) -> Self {
+        Self {
+            elements: value.to_vec(),
+            cap: value.len(),
+        }
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Lifo<T> {
+    // the array elements are moved, not cloned, and put in
+    // array order so the last element is the first to pop
+    fn from(value: [T; N]) -> Self {
+        Self {
+            elements: Vec::from(value),
+            cap: N,
+        }
+    }
+}
+
+impl<T> TryFrom<Vec<T>> for Lifo<T> {
+    type Error = Error;
+
+    // the capacity is the vector length so this cannot fail,
+    // see `try_from_with_capacity` for a fallible conversion
+    fn try_from(value: Vec<T>) -> Result<Self, Self::Error> {
+        let cap = value.len();
+        Self::try_from_with_capacity(value, cap)
+    }
+}
+
+impl<T> From<Lifo<T>> for Vec<T> {
+    // the returned vector is in insertion order, the
+    // reverse of the pop order
+    fn from(value: Lifo<T>) -> Self {
+        value.elements
+    }
+}
+
+/// An iterator that owns a [`Lifo`] and pops its elements,
+/// created by the `into_iter` method of `Lifo`.
+pub struct LifoIntoIter<T> {
+    lifo: Lifo<T>,
+}
+
+impl<T> Iterator for LifoIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lifo.pop()
+    }
+}
+
+impl<T> IntoIterator for Lifo<T> {
+    type Item = T;
+    type IntoIter = LifoIntoIter<T>;
+
+    // elements are yielded in pop order
+    fn into_iter(self) -> Self::IntoIter {
+        LifoIntoIter { lifo: self }
+    }
+}
+
+impl<T> FromIterator<T> for Lifo<T> {
+    // elements are put in iteration order and the capacity
+    // of the queue is the number of collected elements
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let elements: Vec<T> = iter.into_iter().collect();
+        Self {
+            cap: elements.len(),
+            elements,
+        }
+    }
+}
+
+impl<T> Extend<T> for Lifo<T> {
+    // elements are put in iteration order until one doesn't
+    // fit: that element is dropped and the rest of the iterator
+    // is left unconsumed
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            if self.put(item).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+impl<T: Debug> Debug for Lifo<T> {
+    // elements are printed in pop order
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Display> Display for Lifo<T> {
+    // elements are printed in pop order, top of the stack first,
+    // the arrows showing the direction elements move in
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[")?;
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{item}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<T: PartialEq> PartialEq for Lifo<T> {
+    // two queues are equal if they would pop the same
+    // elements in the same order, whatever their capacity
+    fn eq(&self, other: &Self) -> bool {
+        self.elements == other.elements
+    }
+}
+
+// the equality of the elements is an equivalence
+// relation, so is the equality of the queues
+impl<T: Eq> Eq for Lifo<T> {}
+
+impl<T: Hash> Hash for Lifo<T> {
+    // consistent with PartialEq: only the elements are hashed,
+    // in pop order, so two equal queues have the same hash
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for item in self.iter() {
+            item.hash(state);
+        }
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Lifo<T> {
+    // like Vec, queues are compared lexicographically,
+    // element by element in pop order
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for Lifo<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T> Lifo<T> {
+    // the position in the backing vector of the element at
+    // `index` in pop order, panics if out of bounds
+    fn position(&self, index: usize) -> usize {
+        let len = self.elements.len();
+        assert!(
+            index < len,
+            "index out of bounds: the len is {len} but the index is {index}"
+        );
+        len - 1 - index
+    }
+
+    // an unbounded queue is never full, even if its length could
+    // somehow reach usize::MAX (e.g. with zero sized elements)
+    fn is_unbounded(&self) -> bool {
+        self.cap == usize::MAX
+    }
+}
+
+impl<T> Index<usize> for Lifo<T> {
+    type Output = T;
+
+    // index 0 is the next element to pop, i.e. the top of the stack
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.elements[self.position(index)]
+    }
+}
+
+impl<T> IndexMut<usize> for Lifo<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let pos = self.position(index);
+        &mut self.elements[pos]
+    }
+}
+
+#[test]
+fn lifo_stack_semantics() {
+    let mut lifo = Lifo::with_capacity(3);
+    assert!(lifo.is_empty());
+
+    assert!(lifo.put(1).is_ok());
+    assert!(lifo.put(2).is_ok());
+    assert_eq!(lifo.pop(), Some(2));
+
+    assert!(lifo.put(3).is_ok());
+    assert!(lifo.put(4).is_ok());
+    assert_eq!(lifo.pop(), Some(4));
+    assert_eq!(lifo.pop(), Some(3));
+
+    assert!(lifo.put(5).is_ok());
+    assert_eq!(lifo.pop(), Some(5));
+    assert_eq!(lifo.pop(), Some(1));
+
+    assert_eq!(lifo.pop(), None);
+    assert!(lifo.is_empty());
+}
+
+#[test]
+fn lifo_peek_does_not_remove() {
+    let mut lifo = Lifo::with_capacity(2);
+    assert_eq!(lifo.peek(), None);
+
+    assert!(lifo.put("bottom").is_ok());
+    assert!(lifo.put("top").is_ok());
+
+    // peeking several times always returns the same element
+    assert_eq!(lifo.peek(), Some(&"top"));
+    assert_eq!(lifo.peek(), Some(&"top"));
+
+    // while popping removes it
+    assert_eq!(lifo.pop(), Some("top"));
+    assert_eq!(lifo.peek(), Some(&"bottom"));
+    assert_eq!(lifo.pop(), Some("bottom"));
+    assert_eq!(lifo.peek(), None);
+}
+
+#[test]
+fn lifo_conversions() {
+    let mut lifo = Lifo::from([1, 2, 3].as_slice());
+    assert_eq!(lifo.pop(), Some(3));
+    assert_eq!(Vec::from(lifo), vec![1, 2]);
+}
+
+#[test]
+fn lifo_put_on_full_queue() {
+    let mut lifo = Lifo::with_capacity(2);
+    assert_eq!(lifo.put(1), Ok(()));
+    assert_eq!(lifo.put(2), Ok(()));
+    assert_eq!(lifo.put(3), Err(Error::Full));
+
+    // the rejected item never made it in the queue
+    assert_eq!(lifo.pop(), Some(2));
+    assert_eq!(lifo.pop(), Some(1));
+    assert_eq!(lifo.pop(), None);
+}
+
+#[test]
+fn lifo_capacity_is_a_hard_bound() {
+    let mut lifo = Lifo::with_capacity(3);
+    for i in 0..3 {
+        assert!(!lifo.is_full());
+        assert_eq!(lifo.put(i), Ok(()));
+    }
+    assert!(lifo.is_full());
+    assert_eq!(lifo.put(3), Err(Error::Full));
+
+    // popping frees a slot
+    assert_eq!(lifo.pop(), Some(2));
+    assert!(!lifo.is_full());
+    assert_eq!(lifo.put(3), Ok(()));
+    assert!(lifo.is_full());
+}
+
+#[test]
+fn lifo_huge_capacity_is_not_allocated() {
+    let cap = usize::MAX - 1;
+    let mut lifo: Lifo<u8> = Lifo::with_capacity(cap);
+    assert_eq!(lifo.capacity(), cap);
+    assert_eq!(lifo.put(1), Ok(()));
+    assert!(!lifo.is_full());
+
+    let fifo = lifo.into_fifo();
+    assert_eq!(fifo.capacity(), cap);
+}
+
+#[test]
+fn lifo_zero_capacity_is_always_full() {
+    let mut lifo = Lifo::with_capacity(0);
+    assert!(lifo.is_full());
+    assert!(lifo.is_empty());
+    assert_eq!(lifo.put(42), Err(Error::Full));
+    assert_eq!(lifo.pop(), None);
+}
+
+#[test]
+fn lifo_len_and_capacity() {
+    let mut lifo = Lifo::with_capacity(2);
+    assert_eq!(lifo.len(), 0);
+    assert_eq!(lifo.capacity(), 2);
+
+    assert_eq!(lifo.put(1), Ok(()));
+    assert_eq!(lifo.len(), 1);
+    assert_eq!(lifo.put(2), Ok(()));
+    assert_eq!(lifo.len(), 2);
+
+    // a rejected put doesn't change the length
+    assert_eq!(lifo.put(3), Err(Error::Full));
+    assert_eq!(lifo.len(), lifo.capacity());
+
+    assert!(lifo.pop().is_some());
+    assert_eq!(lifo.len(), 1);
+    assert!(lifo.pop().is_some());
+    assert_eq!(lifo.len(), 0);
+    assert!(lifo.pop().is_none());
+    assert_eq!(lifo.len(), 0);
+
+    // the capacity never changes
+    assert_eq!(lifo.capacity(), 2);
+}
+
+#[test]
+fn lifo_into_iter() {
+    let lifo = Lifo::from([1, 2, 3].as_slice());
+    let mut popped = Lifo::from([1, 2, 3].as_slice());
+
+    let collected: Vec<i32> = lifo.into_iter().collect();
+    let mut expected = vec![];
+    while let Some(i) = popped.pop() {
+        expected.push(i);
+    }
+
+    assert_eq!(collected, expected);
+    // last in first out
+    assert_eq!(collected, vec![3, 2, 1]);
+}
+
+#[test]
+fn lifo_iter() {
+    let lifo = Lifo::from([1, 2, 3].as_slice());
+
+    let refs: Vec<&i32> = lifo.iter().collect();
+    assert_eq!(refs, vec![&3, &2, &1]);
+
+    // the queue is left intact
+    assert_eq!(lifo.len(), 3);
+    assert_eq!(lifo.peek(), Some(&3));
+}
+
+#[test]
+fn lifo_drain() {
+    let mut lifo = Lifo::from([0, 1, 2, 3, 4, 5, 6, 7, 8, 9].as_slice());
+
+    let all: Vec<i32> = Lifo::from([0, 1, 2].as_slice()).drain().collect();
+    assert_eq!(all, vec![2, 1, 0]);
+
+    // we only consume half of the iterator
+    let half: Vec<i32> = lifo.drain().take(5).collect();
+    assert_eq!(half, vec![9, 8, 7, 6, 5]);
+
+    // the remaining elements have been dropped with the iterator
+    assert!(lifo.is_empty());
+    assert_eq!(lifo.len(), 0);
+    assert_eq!(lifo.pop(), None);
+}
+
+#[test]
+fn lifo_from_iterator() {
+    let mut lifo: Lifo<_> = (0..5).collect();
+    assert_eq!(lifo.len(), 5);
+    assert_eq!(lifo.capacity(), 5);
+    assert!(lifo.is_full());
+    assert_eq!(lifo.drain().collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn lifo_extend() {
+    let mut lifo = Lifo::with_capacity(5);
+    lifo.extend(0..3);
+    assert_eq!(lifo.len(), 3);
+
+    // extending past the capacity stops once the queue is full
+    lifo.extend(3..10);
+    assert_eq!(lifo.len(), 5);
+    assert!(lifo.is_full());
+    assert_eq!(lifo.drain().collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn lifo_try_put() {
+    let mut lifo = Lifo::with_capacity(2);
+    assert_eq!(lifo.try_put(String::from("hello")), Ok(()));
+    assert_eq!(lifo.try_put(String::from("world")), Ok(()));
+
+    // the queue is full, we get the item back
+    let rejected = lifo.try_put(String::from("rejected")).unwrap_err();
+    assert_eq!(rejected, "rejected");
+    assert_eq!(lifo.len(), 2);
+
+    // once a slot is free, we can retry with the same item
+    assert!(lifo.pop().is_some());
+    assert_eq!(lifo.try_put(rejected), Ok(()));
+    assert!(lifo.iter().any(|s| s == "rejected"));
+}
+
+#[test]
+fn lifo_debug() {
+    let lifo = Lifo::from([1, 2, 3].as_slice());
+    assert_eq!(format!("{lifo:?}"), "[3, 2, 1]");
+}
+
+#[test]
+fn lifo_clone() {
+    let mut lifo = Lifo::from([1, 2, 3].as_slice());
+    let clone = lifo.clone();
+
+    // the clone is independent from the original
+    assert!(lifo.pop().is_some());
+    assert_eq!(lifo.len(), 2);
+    assert_eq!(clone.len(), 3);
+    assert_eq!(clone.capacity(), lifo.capacity());
+}
+
+#[test]
+fn lifo_partial_eq() {
+    let a = Lifo::from([1, 2, 3].as_slice());
+    let mut b = Lifo::with_capacity(10);
+    b.extend([1, 2, 3]);
+
+    // same elements in the same order but different capacities
+    assert_eq!(a, b);
+
+    assert!(b.pop().is_some());
+    assert_ne!(a, b);
+    assert_ne!(a, Lifo::from([3, 2, 1].as_slice()));
+}
+
+#[test]
+fn lifo_peek_mut() {
+    let mut lifo = Lifo::from([1, 2, 3].as_slice());
+
+    if let Some(next) = lifo.peek_mut() {
+        *next *= 10;
+    }
+    assert_eq!(lifo.len(), 3);
+    assert_eq!(lifo.pop(), Some(30));
+
+    let mut empty: Lifo<i32> = Lifo::with_capacity(1);
+    assert_eq!(empty.peek_mut(), None);
+}
+
+#[test]
+fn lifo_clear() {
+    let mut lifo = Lifo::with_capacity(3);
+    lifo.extend(0..3);
+    assert!(lifo.is_full());
+
+    lifo.clear();
+    assert!(lifo.is_empty());
+    assert_eq!(lifo.len(), 0);
+    assert_eq!(lifo.capacity(), 3);
+
+    // the queue can be filled again
+    lifo.extend(3..6);
+    assert!(lifo.is_full());
+    assert_eq!(lifo.pop(), Some(5));
+}
+
+#[test]
+fn lifo_reserve() {
+    let mut lifo = Lifo::with_capacity(2);
+    lifo.extend(0..2);
+    assert!(lifo.is_full());
+
+    assert_eq!(lifo.reserve(3), Ok(()));
+    assert_eq!(lifo.capacity(), 5);
+    assert_eq!(lifo.len(), 2);
+    assert!(!lifo.is_full());
+    assert_eq!(lifo.put(2), Ok(()));
+
+    // there is already room for one more element
+    // so the capacity doesn't change
+    assert_eq!(lifo.reserve(1), Ok(()));
+    assert_eq!(lifo.capacity(), 5);
+}
+
+#[test]
+fn lifo_contains() {
+    let lifo = Lifo::from([1, 2, 3].as_slice());
+    assert!(lifo.contains(&2));
+    assert!(!lifo.contains(&42));
+
+    let empty: Lifo<i32> = Lifo::with_capacity(1);
+    assert!(!empty.contains(&1));
+}
+
+#[test]
+fn lifo_retain() {
+    let mut lifo: Lifo<i32> = (0..10).collect();
+    lifo.retain(|i| i % 2 == 0);
+
+    assert_eq!(lifo.len(), 5);
+    assert_eq!(lifo.drain().collect::<Vec<_>>(), vec![8, 6, 4, 2, 0]);
+}
+
+#[test]
+fn lifo_into_fifo() {
+    let lifo = Lifo::from([3, 2, 1].as_slice());
+    let mut fifo = lifo.clone().into_fifo();
+    assert_eq!(fifo.capacity(), lifo.capacity());
+
+    let mut popped = vec![];
+    for item in lifo {
+        assert_eq!(fifo.pop(), Some(item));
+        popped.push(item);
+    }
+    assert_eq!(popped, vec![1, 2, 3]);
+    assert!(fifo.is_empty());
+}
+
+#[test]
+fn lifo_append() {
+    let mut a = Lifo::with_capacity(4);
+    a.extend([1, 2]);
+    let mut b = Lifo::from([3, 4].as_slice());
+
+    assert_eq!(a.append(&mut b), Ok(()));
+    assert!(b.is_empty());
+    assert_eq!(a.drain().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+}
+
+#[test]
+fn lifo_append_overflow() {
+    let mut a = Lifo::from([1, 2].as_slice());
+    let mut b = Lifo::from([3].as_slice());
+
+    assert_eq!(a.append(&mut b), Err(Error::Full));
+    // nothing has been moved
+    assert_eq!(a.len(), 2);
+    assert_eq!(b.len(), 1);
+}
+
+#[test]
+fn lifo_as_slice() {
+    let mut lifo = Lifo::from([3, 1, 2].as_slice());
+    assert_eq!(lifo.peek(), Some(&2));
+    assert_eq!(lifo.as_slice(), &[3, 1, 2]);
+
+    // sorting the slice in descending order gives an
+    // ascending pop order
+    lifo.as_mut_slice().sort_by(|a, b| b.cmp(a));
+    assert_eq!(lifo.as_slice(), &[3, 2, 1]);
+    // the top of the stack is the end of the slice
+    assert_eq!(lifo.peek(), Some(&1));
+
+    // pop order is the reverse of the slice order
+    let mut slice = lifo.as_slice().to_vec();
+    slice.reverse();
+    assert_eq!(lifo.drain().collect::<Vec<_>>(), slice);
+}
+
+#[test]
+fn lifo_map() {
+    let lifo = Lifo::from([1, 2, 3].as_slice());
+    let mut strings: Lifo<String> = lifo.map(|i| i.to_string());
+
+    assert_eq!(strings.capacity(), 3);
+    assert_eq!(strings.pop(), Some(String::from("3")));
+    assert_eq!(strings.pop(), Some(String::from("2")));
+    assert_eq!(strings.pop(), Some(String::from("1")));
+}
+
+#[test]
+fn lifo_index() {
+    let mut lifo = Lifo::with_capacity(4);
+    lifo.extend([1, 2, 3]);
+    // indexes follow the pop order
+    assert_eq!(lifo[0], 3);
+    assert_eq!(lifo[2], 1);
+
+    lifo[1] = 20;
+    assert_eq!(lifo.pop(), Some(3));
+    assert_eq!(lifo[0], 20);
+    assert_eq!(lifo.pop(), Some(20));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+fn lifo_index_out_of_bounds() {
+    let lifo = Lifo::from([1, 2].as_slice());
+    let _ = lifo[2];
+}
+
+#[test]
+fn lifo_iter_rev() {
+    let lifo = Lifo::from([1, 2, 3].as_slice());
+
+    let forward: Vec<&i32> = lifo.iter().collect();
+    let backward: Vec<&i32> = lifo.iter().rev().collect();
+    assert_eq!(forward, vec![&3, &2, &1]);
+    assert_eq!(backward, vec![&1, &2, &3]);
+
+    // both are mirror images
+    assert!(forward.iter().eq(backward.iter().rev()));
+}
+
+#[test]
+fn lifo_filled_with_default() {
+    let mut lifo: Lifo<String> = Lifo::filled_with_default(3);
+    assert_eq!(lifo.len(), 3);
+    assert!(lifo.is_full());
+    assert!(lifo.drain().all(|s| s == String::default()));
+
+    let lifo: Lifo<i32> = Lifo::filled_with_default(0);
+    assert!(lifo.is_empty());
+    assert!(lifo.is_full());
+}
+
+#[test]
+fn lifo_peek_n() {
+    let lifo = Lifo::from([1, 2, 3].as_slice());
+    assert_eq!(lifo.peek_n(2), vec![&3, &2]);
+    assert_eq!(lifo.peek_n(3), vec![&3, &2, &1]);
+    // n is clamped to the number of elements
+    assert_eq!(lifo.peek_n(10), vec![&3, &2, &1]);
+    assert!(lifo.peek_n(0).is_empty());
+
+    // nothing has been popped
+    assert_eq!(lifo.len(), 3);
+}
+
+#[test]
+fn lifo_pop_n() {
+    let mut lifo = Lifo::from([1, 2, 3, 4, 5].as_slice());
+    assert_eq!(lifo.pop_n(2), vec![5, 4]);
+    assert_eq!(lifo.len(), 3);
+
+    assert!(lifo.pop_n(0).is_empty());
+    assert_eq!(lifo.len(), 3);
+
+    assert_eq!(lifo.pop_n(1), vec![3]);
+    assert_eq!(lifo.len(), 2);
+
+    // asking for more than available pops everything
+    assert_eq!(lifo.pop_n(10), vec![2, 1]);
+    assert!(lifo.is_empty());
+    assert!(lifo.pop_n(1).is_empty());
+}
+
+#[test]
+fn lifo_new_is_unbounded() {
+    let mut lifo = Lifo::new();
+    assert!(lifo.is_empty());
+    assert!(!lifo.is_full());
+    assert_eq!(lifo.capacity(), usize::MAX);
+
+    // other conversions keep the queue unbounded
+    let other = lifo.clone().into_fifo();
+    assert_eq!(other.capacity(), usize::MAX);
+
+    lifo.extend(0..100);
+    assert_eq!(lifo.len(), 100);
+    assert_eq!(lifo.pop(), Some(99));
+}
+
+#[test]
+fn lifo_hash() {
+    use std::collections::HashSet;
+
+    let mut a = Lifo::with_capacity(10);
+    a.extend([1, 2, 3]);
+    let b = Lifo::from([1, 2, 3].as_slice());
+    let c = Lifo::from([3, 2, 1].as_slice());
+
+    let mut set = HashSet::new();
+    assert!(set.insert(a));
+    // b is equal to a even though their capacities differ
+    assert!(!set.insert(b));
+    assert!(set.insert(c));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn lifo_shrink_to_fit() {
+    let mut lifo = Lifo::with_capacity(4);
+    lifo.extend([1, 2]);
+    assert_eq!(lifo.reserve(100), Ok(()));
+    assert!(lifo.spare_capacity() >= 100);
+
+    lifo.shrink_to_fit();
+    assert!(lifo.spare_capacity() < 100);
+    assert_eq!(lifo.len(), 2);
+    // the logical bound is unchanged
+    assert_eq!(lifo.capacity(), 102);
+    assert_eq!(lifo.put(3), Ok(()));
+}
+
+#[test]
+fn lifo_rotate_left() {
+    // pops 1, 2, 3, 4
+    let mut lifo = Lifo::from([4, 3, 2, 1].as_slice());
+
+    lifo.rotate_left(1);
+    assert_eq!(lifo.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 1]);
+
+    // rotating by len is a no-op
+    lifo.rotate_left(4);
+    assert_eq!(lifo.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 1]);
+
+    // mid is taken modulo len
+    lifo.rotate_left(7);
+    assert_eq!(lifo.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+    let mut empty: Lifo<i32> = Lifo::new();
+    empty.rotate_left(3);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn lifo_dedup_consecutive() {
+    let mut lifo = Lifo::from([2, 3, 3, 3, 2, 1, 1].as_slice());
+    lifo.dedup_consecutive();
+    assert_eq!(lifo.drain().collect::<Vec<_>>(), [1, 2, 3, 2]);
+
+    let mut distinct = Lifo::from([1, 2, 3].as_slice());
+    distinct.dedup_consecutive();
+    assert_eq!(distinct, Lifo::from([1, 2, 3].as_slice()));
+
+    let mut empty: Lifo<i32> = Lifo::new();
+    empty.dedup_consecutive();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn lifo_min_max() {
+    let lifo = Lifo::from([3, 1, 4, 1, 5].as_slice());
+    assert_eq!(lifo.min_element(), Some(&1));
+    assert_eq!(lifo.max_element(), Some(&5));
+    // the queue is not consumed
+    assert_eq!(lifo.len(), 5);
+
+    let words: Lifo<String> = ["pear", "apple", "fig"]
+        .map(String::from)
+        .into_iter()
+        .collect();
+    assert_eq!(words.min_element().map(String::as_str), Some("apple"));
+    assert_eq!(words.max_element().map(String::as_str), Some("pear"));
+
+    let empty: Lifo<i32> = Lifo::new();
+    assert_eq!(empty.min_element(), None);
+    assert_eq!(empty.max_element(), None);
+}
+
+#[test]
+fn lifo_try_from_vec() {
+    let mut lifo = Lifo::try_from(vec![1, 2, 3]).unwrap();
+    assert_eq!(lifo.capacity(), 3);
+    assert!(lifo.is_full());
+    assert_eq!(lifo.drain().collect::<Vec<_>>(), [3, 2, 1]);
+
+    let mut lifo = Lifo::try_from_with_capacity(vec![1, 2], 4).unwrap();
+    assert_eq!(lifo.capacity(), 4);
+    assert_eq!(lifo.put(3), Ok(()));
+    assert_eq!(lifo.pop(), Some(3));
+
+    assert_eq!(
+        Lifo::try_from_with_capacity(vec![1, 2, 3], 2).unwrap_err(),
+        Error::Capacity(3)
+    );
+}
+
+#[test]
+fn lifo_reserve_overflow() {
+    let mut lifo = Lifo::from([1].as_slice());
+    // 1 + usize::MAX overflows
+    assert_eq!(lifo.reserve(usize::MAX), Err(Error::Capacity(usize::MAX)));
+    // no overflow but that much memory cannot be allocated
+    let mut empty: Lifo<u64> = Lifo::with_capacity(0);
+    assert_eq!(empty.reserve(usize::MAX), Err(Error::Capacity(usize::MAX)));
+    assert_eq!(empty.capacity(), 0);
+
+    // the queue is unchanged
+    assert_eq!(lifo.capacity(), 1);
+    assert_eq!(lifo.reserve(2), Ok(()));
+    assert_eq!(lifo.capacity(), 3);
+}
+
+#[test]
+fn lifo_from_array() {
+    let mut lifo = Lifo::from([1, 2, 3]);
+    assert_eq!(lifo.capacity(), 3);
+    assert!(lifo.is_full());
+    assert_eq!(lifo.pop(), Some(3));
+
+    // no Clone bound: the elements are moved into the queue
+    struct NotClone(u8);
+    let mut lifo = Lifo::from([NotClone(1), NotClone(2), NotClone(3)]);
+    assert_eq!(lifo.pop().map(|n| n.0), Some(3));
+
+    let empty: Lifo<NotClone> = Lifo::from([]);
+    assert!(empty.is_full());
+}
+
+#[test]
+fn lifo_to_vec_pop_order() {
+    let lifo = Lifo::from([1, 2, 3]);
+    let snapshot = lifo.to_vec_pop_order();
+
+    let mut popped = Vec::new();
+    let mut clone = lifo.clone();
+    while let Some(&front) = clone.peek() {
+        assert_eq!(clone.pop(), Some(front));
+        popped.push(front);
+    }
+    assert_eq!(snapshot, popped);
+
+    // the original queue is unchanged
+    assert_eq!(lifo, Lifo::from([1, 2, 3]));
+    assert!(Lifo::<i32>::new().to_vec_pop_order().is_empty());
+}
+
+#[test]
+fn lifo_fold() {
+    let lifo = Lifo::from(["a", "b", "c"]);
+    let joined = lifo.fold(String::new(), |mut acc, s| {
+        acc.push_str(s);
+        acc
+    });
+    // the elements are folded in pop order
+    assert_eq!(joined, "cba");
+    assert_eq!(lifo.len(), 3);
+
+    assert_eq!(Lifo::<&str>::new().fold(0, |acc, s| acc + s.len()), 0);
+}
+
+#[test]
+fn lifo_filter_into() {
+    let lifo: Lifo<i32> = (0..6).collect();
+    let mut odds = lifo.filter_into(|x| x % 2 == 1);
+    assert_eq!(odds.capacity(), 3);
+    assert!(odds.is_full());
+    assert_eq!(odds.drain().collect::<Vec<_>>(), [5, 3, 1]);
+
+    let none = Lifo::from([1, 2, 3]).filter_into(|_| false);
+    assert!(none.is_empty());
+    assert_eq!(none.capacity(), 0);
+}
+
+#[test]
+fn lifo_ordering() {
+    // a Lifo popping the items in the given order
+    let pops = |items: &[i32]| items.iter().copied().rev().collect::<Lifo<_>>();
+    assert!(pops(&[1, 2]) < pops(&[1, 3]));
+    // a prefix is smaller
+    assert!(pops(&[1]) < pops(&[1, 2]));
+    assert!(pops(&[]) < pops(&[0]));
+    assert!(pops(&[2]) > pops(&[1, 5]));
+    // Ord::min and Ord::max compare whole queues (see min_element)
+    assert_eq!(pops(&[1, 2]).min(pops(&[0])), pops(&[0]));
+    assert_eq!(pops(&[1, 2]).max(pops(&[0])), pops(&[1, 2]));
+
+    let mut queues = vec![pops(&[2]), pops(&[1, 3]), pops(&[1]), pops(&[1, 2])];
+    queues.sort();
+    assert_eq!(
+        queues,
+        [pops(&[1]), pops(&[1, 2]), pops(&[1, 3]), pops(&[2])]
+    );
+}
+
+#[test]
+fn lifo_split_off() {
+    let mut lifo = Lifo::from([4, 3, 2, 1]);
+    let mut rest = lifo.split_off(2);
+    assert_eq!(rest.capacity(), 4);
+    assert_eq!(lifo.drain().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(rest.drain().collect::<Vec<_>>(), [3, 4]);
+
+    let mut lifo = Lifo::from([1, 2]);
+    assert!(lifo.split_off(2).is_empty());
+    assert_eq!(lifo.split_off(0), Lifo::from([1, 2]));
+    assert!(lifo.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "split index (is 3) should be <= len (is 2)")]
+fn lifo_split_off_out_of_range() {
+    Lifo::from([1, 2]).split_off(3);
+}
+
+#[test]
+fn lifo_saturating_put() {
+    let mut lifo = Lifo::with_capacity(2);
+    assert!(lifo.saturating_put(1));
+    assert!(lifo.saturating_put(2));
+    assert!(!lifo.saturating_put(3));
+    assert_eq!(lifo.len(), 2);
+    assert!(!lifo.contains(&3));
+}
+
+#[test]
+fn lifo_display() {
+    let lifo = Lifo::from([1, 2, 3]);
+    assert_eq!(lifo.to_string(), "[3 -> 2 -> 1]");
+    // distinct from Debug
+    assert_eq!(format!("{lifo:?}"), "[3, 2, 1]");
+
+    assert_eq!(Lifo::from([1]).to_string(), "[1]");
+    assert_eq!(Lifo::<i32>::new().to_string(), "[]");
+}
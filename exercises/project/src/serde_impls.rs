@@ -0,0 +1,164 @@
+//! Serialization of the queues, enabled with the `serde` feature.
+//!
+//! A queue is serialized as its elements, in pop order, and its
+//! capacity. Deserializing gives back a queue popping the same
+//! elements in the same order. The overwrite mode and the weight
+//! limit of a `Fifo` are not serialized (a function cannot be),
+//! a deserialized `Fifo` is in default mode.
+
+use alloc::vec::Vec;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Fifo, Lifo};
+// the tests below use the Queue methods
+#[cfg(test)]
+use crate::Queue;
+
+// the layout shared by all the serialized queues
+#[derive(Deserialize)]
+struct Repr<T> {
+    elements: Vec<T>,
+    capacity: usize,
+}
+
+impl<T: Serialize> Serialize for Fifo<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let elements: Vec<&T> = self.iter().collect();
+        let mut state = serializer.serialize_struct("Fifo", 2)?;
+        state.serialize_field("elements", &elements)?;
+        state.serialize_field("capacity", &self.capacity())?;
+        state.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Fifo<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Repr::deserialize(deserializer)?;
+
+        // the capacity comes from the input and may be huge, it is
+        // only a bound so the queue is built from the elements
+        // without allocating room for `capacity` of them
+        //
+        // elements are in pop order, which is the put order for a Fifo
+        Fifo::try_from_with_capacity(repr.elements, repr.capacity).map_err(serde::de::Error::custom)
+    }
+}
+
+impl<T: Serialize> Serialize for Lifo<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let elements: Vec<&T> = self.iter().collect();
+        let mut state = serializer.serialize_struct("Lifo", 2)?;
+        state.serialize_field("elements", &elements)?;
+        state.serialize_field("capacity", &self.capacity())?;
+        state.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Lifo<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Repr::deserialize(deserializer)?;
+
+        // elements are in pop order, the reverse of the put order for a
+        // Lifo, and as for a Fifo the capacity is not allocated
+        let mut elements = repr.elements;
+        elements.reverse();
+        Lifo::try_from_with_capacity(elements, repr.capacity).map_err(serde::de::Error::custom)
+    }
+}
+
+#[test]
+fn serde_fifo_round_trip() {
+    let mut fifo = Fifo::with_capacity(3);
+    fifo.extend([1, 2, 3]);
+    assert!(fifo.is_full());
+
+    let json = serde_json::to_string(&fifo).unwrap();
+    assert_eq!(json, r#"{"elements":[1,2,3],"capacity":3}"#);
+
+    let mut de: Fifo<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(de, fifo);
+    assert!(de.is_full());
+    assert_eq!(de.pop(), Some(1));
+}
+
+#[test]
+fn serde_lifo_round_trip() {
+    let mut lifo = Lifo::with_capacity(3);
+    lifo.extend([1, 2, 3]);
+    assert!(lifo.is_full());
+
+    let json = serde_json::to_string(&lifo).unwrap();
+    // elements are serialized in pop order
+    assert_eq!(json, r#"{"elements":[3,2,1],"capacity":3}"#);
+
+    let mut de: Lifo<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(de, lifo);
+    assert!(de.is_full());
+    assert_eq!(de.pop(), Some(3));
+}
+
+#[test]
+fn serde_empty_queues() {
+    let fifo: Fifo<String> = Fifo::with_capacity(2);
+    let json = serde_json::to_string(&fifo).unwrap();
+    let de: Fifo<String> = serde_json::from_str(&json).unwrap();
+    assert!(de.is_empty());
+    assert_eq!(de.capacity(), 2);
+
+    let lifo: Lifo<String> = Lifo::with_capacity(0);
+    let json = serde_json::to_string(&lifo).unwrap();
+    let de: Lifo<String> = serde_json::from_str(&json).unwrap();
+    assert!(de.is_empty());
+    assert!(de.is_full());
+}
+
+#[test]
+fn serde_unbounded_queues() {
+    let mut fifo = Fifo::new();
+    fifo.extend([1, 2]);
+    let json = serde_json::to_string(&fifo).unwrap();
+    let de: Fifo<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(de, fifo);
+    assert_eq!(de.capacity(), usize::MAX);
+
+    let lifo: Lifo<i32> = Lifo::new();
+    let json = serde_json::to_string(&lifo).unwrap();
+    let de: Lifo<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(de.capacity(), usize::MAX);
+}
+
+#[test]
+fn serde_too_many_elements() {
+    let res: Result<Fifo<i32>, _> = serde_json::from_str(r#"{"elements":[1,2,3],"capacity":2}"#);
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "cannot provide a capacity of 3"
+    );
+    let res: Result<Lifo<i32>, _> = serde_json::from_str(r#"{"elements":[1,2,3],"capacity":2}"#);
+    assert!(res.is_err());
+}
+
+#[test]
+fn serde_error() {
+    use crate::Error;
+
+    let json = serde_json::to_string(&Error::Capacity(42)).unwrap();
+    assert_eq!(json, r#"{"Capacity":42}"#);
+    let de: Error = serde_json::from_str(r#""Full""#).unwrap();
+    assert_eq!(de, Error::Full);
+}
+
+#[test]
+fn serde_huge_capacity() {
+    let json = r#"{"elements":[1,2],"capacity":18446744073709551614}"#;
+    let mut fifo: Fifo<i32> = serde_json::from_str(json).unwrap();
+    assert_eq!(fifo.capacity(), usize::MAX - 1);
+    assert_eq!(fifo.pop(), Some(1));
+
+    let json = r#"{"elements":[2,1],"capacity":4611686018427387904}"#;
+    let mut lifo: Lifo<i32> = serde_json::from_str(json).unwrap();
+    assert_eq!(lifo.capacity(), 1 << 62);
+    assert_eq!(lifo.pop(), Some(2));
+    assert_eq!(lifo.pop(), Some(1));
+}
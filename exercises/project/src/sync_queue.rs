@@ -0,0 +1,143 @@
+use std::marker::PhantomData;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{Error, Len, Queue};
+
+/// A wrapper making any [`Queue`] usable from several threads.
+///
+/// The queue is protected by a [`Mutex`] and every method locks it
+/// for the duration of a single queue operation. All methods take
+/// `&self`, so a `SyncQueue` can be shared with an `Arc`.
+///
+/// As the lock is released when a method returns, we cannot hand
+/// out references to the elements: `peek_cloned` returns a clone
+/// of the next element instead of a reference.
+pub struct SyncQueue<T, Q: Queue<T>> {
+    queue: Mutex<Q>,
+    // the queue holds T values, the wrapper itself doesn't, so
+    // we use fn() -> T not to restrict when it is Send or Sync
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, Q: Queue<T>> SyncQueue<T, Q> {
+    /// Wraps an existing queue.
+    pub fn new(queue: Q) -> Self {
+        Self {
+            queue: Mutex::new(queue),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a wrapper around an empty queue able
+    /// to hold at most `cap` elements.
+    pub fn with_capacity(cap: usize) -> Self {
+        Self::new(Q::with_capacity(cap))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Q> {
+        // the lock is poisoned if a thread panicked while holding
+        // it, as we only hold it for a single queue operation the
+        // queue is still consistent and can keep being used
+        self.queue
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn put(&self, item: T) -> Result<(), Error> {
+        self.lock().put(item)
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        self.lock().pop()
+    }
+
+    /// Returns a clone of the next element to pop.
+    pub fn peek_cloned(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.lock().peek().cloned()
+    }
+
+    pub fn len(&self) -> usize
+    where
+        Q: Len,
+    {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.lock().is_full()
+    }
+
+    /// Consumes the wrapper and returns the inner queue.
+    pub fn into_inner(self) -> Q {
+        self.queue
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[test]
+fn sync_queue_across_threads() {
+    use crate::{Fifo, Lifo};
+    use std::sync::Arc;
+    use std::thread;
+
+    const THREADS: usize = 8;
+    const PER_THREAD: usize = 100;
+
+    fn push_from_threads<Q: Queue<usize> + Send + 'static>() -> Arc<SyncQueue<usize, Q>> {
+        let queue = Arc::new(SyncQueue::with_capacity(THREADS * PER_THREAD));
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for i in 0..PER_THREAD {
+                        assert_eq!(queue.put(t * PER_THREAD + i), Ok(()));
+                    }
+                })
+            })
+            .collect();
+
+        for h in handles {
+            h.join().unwrap();
+        }
+        queue
+    }
+
+    let fifo = push_from_threads::<Fifo<usize>>();
+    assert_eq!(fifo.len(), THREADS * PER_THREAD);
+    assert!(fifo.is_full());
+    let mut popped = 0;
+    while fifo.pop().is_some() {
+        popped += 1;
+    }
+    assert_eq!(popped, THREADS * PER_THREAD);
+
+    let lifo = push_from_threads::<Lifo<usize>>();
+    let mut popped = 0;
+    while lifo.pop().is_some() {
+        popped += 1;
+    }
+    assert_eq!(popped, THREADS * PER_THREAD);
+    assert!(lifo.is_empty());
+}
+
+#[test]
+fn sync_queue_peek_cloned() {
+    use crate::Fifo;
+
+    let queue = SyncQueue::new(Fifo::from([String::from("hello")].as_slice()));
+    assert_eq!(queue.peek_cloned(), Some(String::from("hello")));
+    // peeking doesn't remove the element
+    assert_eq!(queue.len(), 1);
+
+    let fifo = queue.into_inner();
+    assert_eq!(fifo.peek(), Some(&String::from("hello")));
+}
@@ -0,0 +1,76 @@
+//! Property based tests of the queues: random sequences of
+//! `put`/`pop` operations are applied both to a queue and to a
+//! reference model built on std collections, and the queue must
+//! always agree with the model. On failure, proptest shrinks the
+//! sequence to a minimal reproducer.
+
+use std::collections::VecDeque;
+
+use project::{Error, Fifo, Lifo, Queue};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Put(i32),
+    Pop,
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![any::<i32>().prop_map(Op::Put), Just(Op::Pop)]
+}
+
+proptest! {
+    #[test]
+    fn fifo_behaves_like_a_vecdeque(cap in 0usize..16, ops in prop::collection::vec(op(), 0..64)) {
+        let mut fifo = Fifo::with_capacity(cap);
+        let mut model = VecDeque::new();
+
+        for op in ops {
+            match op {
+                Op::Put(i) => {
+                    if model.len() < cap {
+                        prop_assert_eq!(fifo.put(i), Ok(()));
+                        model.push_back(i);
+                    } else {
+                        prop_assert_eq!(fifo.put(i), Err(Error::Full));
+                    }
+                }
+                // a Fifo pops in insertion order
+                Op::Pop => prop_assert_eq!(fifo.pop(), model.pop_front()),
+            }
+
+            prop_assert!(fifo.len() <= fifo.capacity());
+            prop_assert_eq!(fifo.len(), model.len());
+            prop_assert_eq!(fifo.is_empty(), fifo.len() == 0);
+            prop_assert_eq!(fifo.is_full(), fifo.len() == cap);
+            prop_assert_eq!(fifo.peek(), model.front());
+        }
+    }
+
+    #[test]
+    fn lifo_behaves_like_a_vec(cap in 0usize..16, ops in prop::collection::vec(op(), 0..64)) {
+        let mut lifo = Lifo::with_capacity(cap);
+        let mut model = Vec::new();
+
+        for op in ops {
+            match op {
+                Op::Put(i) => {
+                    if model.len() < cap {
+                        prop_assert_eq!(lifo.put(i), Ok(()));
+                        model.push(i);
+                    } else {
+                        prop_assert_eq!(lifo.put(i), Err(Error::Full));
+                    }
+                }
+                // a Lifo pops in reverse insertion order
+                Op::Pop => prop_assert_eq!(lifo.pop(), model.pop()),
+            }
+
+            prop_assert!(lifo.len() <= lifo.capacity());
+            prop_assert_eq!(lifo.len(), model.len());
+            prop_assert_eq!(lifo.is_empty(), lifo.len() == 0);
+            prop_assert_eq!(lifo.is_full(), lifo.len() == cap);
+            prop_assert_eq!(lifo.peek(), model.last());
+        }
+    }
+}
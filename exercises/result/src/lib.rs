@@ -132,30 +132,67 @@ fn common_methods() {
     // println!("res_err.unwrap()={}", res_err.unwrap());
 }
 
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    // the string is not an integer
+    NotANumber(std::num::ParseIntError),
+    // the string is an integer but not a positive one
+    NotPositive(i32),
+}
+
+/// Parses a positive integer out of a string.
+///
+/// It chains two operations which can both fail: `map_err` turns
+/// the parsing error into our own error type and `and_then` runs
+/// the positivity check only if the parsing succeeded.
+pub fn parse_positive(s: &str) -> Result<i32, ParseError> {
+    s.parse::<i32>()
+        .map_err(ParseError::NotANumber)
+        .and_then(|i| {
+            if i.is_positive() {
+                Ok(i)
+            } else {
+                Err(ParseError::NotPositive(i))
+            }
+        })
+}
+
+#[test]
+fn and_then_chaining() {
+    assert_eq!(parse_positive("42"), Ok(42));
+    assert_eq!(parse_positive("-1"), Err(ParseError::NotPositive(-1)));
+    assert_eq!(parse_positive("0"), Err(ParseError::NotPositive(0)));
+    assert!(matches!(
+        parse_positive("abc"),
+        Err(ParseError::NotANumber(_))
+    ));
+}
+
 /// In practice Results are used for proper error handling.
 /// Most of the times errors can be of different kinds so
 /// it is a very common pattern to use an enum Error type
 /// to return an error. In this way we are able to handle
 /// each kind of error separately.
-#[test]
-#[allow(dead_code)]
-fn result_in_practice() {
-    #[derive(Debug)]
-    enum Error {
-        IsZero,
-        IsNegative(i32),
-    }
+#[derive(Debug, PartialEq)]
+pub enum NumberError {
+    IsZero,
+    IsNegative(i32),
+}
 
-    fn handle_positive_number(i: i32) -> Result<i32, Error> {
-        if i == 0 {
-            Err(Error::IsZero)
-        } else if i.is_negative() {
-            Err(Error::IsNegative(i))
-        } else {
-            Ok(i)
-        }
+/// Returns `i` if it is strictly positive, or a [`NumberError`]
+/// telling why it is not.
+pub fn handle_positive_number(i: i32) -> Result<i32, NumberError> {
+    if i == 0 {
+        Err(NumberError::IsZero)
+    } else if i.is_negative() {
+        Err(NumberError::IsNegative(i))
+    } else {
+        Ok(i)
     }
+}
 
+#[test]
+fn result_in_practice() {
     let i = 42;
     println!("input={i} result={:?}", handle_positive_number(i));
 
@@ -166,5 +203,26 @@ fn result_in_practice() {
     println!("input={i} result={:?}", handle_positive_number(i));
 
     // EXERCISE: make an example where you handle the result of `handle_positive_number`
-    // and where you print a different message for each variant of Error enum.
+    // and where you print a different message for each variant of NumberError enum.
+}
+
+/// A solution to the exercise above: as the error is an enum we
+/// can match on it and build a different message for each variant.
+pub fn describe(err: &NumberError) -> String {
+    match err {
+        NumberError::IsZero => String::from("zero is neither positive nor negative"),
+        NumberError::IsNegative(i) => format!("{i} is negative"),
+    }
+}
+
+#[test]
+fn result_describe() {
+    assert_eq!(handle_positive_number(42), Ok(42));
+
+    let err = handle_positive_number(0).unwrap_err();
+    assert_eq!(describe(&err), "zero is neither positive nor negative");
+
+    let err = handle_positive_number(-42).unwrap_err();
+    assert_eq!(err, NumberError::IsNegative(-42));
+    assert_eq!(describe(&err), "-42 is negative");
 }
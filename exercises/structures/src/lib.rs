@@ -226,6 +226,54 @@ fn structure_method() {
     println!("rect_tuple={rect_tuple:?}");
 }
 
+/// All the methods seen above gathered on a single `Rectangle`,
+/// along with a way to compare the size of two rectangles.
+#[derive(Debug)]
+pub struct Rectangle {
+    width: u32,
+    height: u32,
+}
+
+impl Rectangle {
+    pub fn from_tuple(t: (u32, u32)) -> Self {
+        Self {
+            width: t.0,
+            height: t.1,
+        }
+    }
+
+    pub fn into_tuple(self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    /// Returns true if `self` is strictly larger than `other`
+    /// in both dimensions.
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width > other.width && self.height > other.height
+    }
+}
+
+#[test]
+fn rectangle_can_hold() {
+    let r = Rectangle::from_tuple((8, 7));
+    assert_eq!(r.area(), 56);
+
+    // strictly larger
+    assert!(r.can_hold(&Rectangle::from_tuple((5, 1))));
+    assert!(!Rectangle::from_tuple((5, 1)).can_hold(&r));
+    // equal
+    assert!(!r.can_hold(&Rectangle::from_tuple((8, 7))));
+    // smaller in one dimension only
+    assert!(!r.can_hold(&Rectangle::from_tuple((9, 1))));
+    assert!(!r.can_hold(&Rectangle::from_tuple((1, 7))));
+
+    assert_eq!(r.into_tuple(), (8, 7));
+}
+
 /// EXERCISE: following what we have seen above
 /// implement a Circle structure with the following
 /// methods:
@@ -233,5 +281,42 @@ fn structure_method() {
 /// - a method to compute its area
 /// - a modifier for the radius
 /// - a method that returns true if a circle can hold another one
+///
+/// Here is a possible solution.
+#[derive(Debug)]
+pub struct Circle {
+    radius: f64,
+}
+
+impl Circle {
+    pub fn new(radius: f64) -> Self {
+        Self { radius }
+    }
+
+    pub fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+
+    pub fn set_radius(&mut self, r: f64) {
+        self.radius = r
+    }
+
+    /// Returns true if `other` fits strictly inside `self`.
+    pub fn can_hold(&self, other: &Circle) -> bool {
+        self.radius > other.radius
+    }
+}
+
 #[test]
-fn circle_exercise() {}
+fn circle_exercise() {
+    let mut c = Circle::new(2.0);
+    assert!((c.area() - 4.0 * std::f64::consts::PI).abs() < 1e-9);
+
+    c.set_radius(3.0);
+    assert!((c.area() - 9.0 * std::f64::consts::PI).abs() < 1e-9);
+
+    let small = Circle::new(1.0);
+    assert!(c.can_hold(&small));
+    assert!(!small.can_hold(&c));
+    assert!(!small.can_hold(&Circle::new(1.0)));
+}
@@ -69,9 +69,10 @@ fn default_implementation() {
         fn greet(&self) -> String;
 
         // we provide a default implementation for
-        // print_greeting function
-        fn print_greeting(&self) {
-            println!("{}", self.greet())
+        // print_greeting function, it returns the greeting
+        // rather than printing it so we can test it
+        fn print_greeting(&self) -> String {
+            self.greet()
         }
     }
 
@@ -91,10 +92,12 @@ fn default_implementation() {
     }
 
     let cat = Cat;
-    cat.print_greeting();
+    println!("{}", cat.print_greeting());
+    assert_eq!(cat.print_greeting(), "Meow!");
 
     let human = Human;
-    human.print_greeting();
+    println!("{}", human.print_greeting());
+    assert_eq!(human.print_greeting(), "Hi!");
 }
 
 /// Let's explore how to use trait bounds with generics to
@@ -129,6 +132,93 @@ fn traits_and_generics() {
     // }
 }
 
+/// Here is how the exercise above can be solved: `greet` needs
+/// to know `T` implements `Greet`, which we tell the compiler with
+/// a TRAIT BOUND.
+pub trait Greet {
+    fn greet(&self) -> String;
+}
+
+pub struct Human;
+
+impl Greet for Human {
+    fn greet(&self) -> String {
+        String::from("Hi!")
+    }
+}
+
+pub struct Cat;
+
+impl Greet for Cat {
+    fn greet(&self) -> String {
+        String::from("Meow!")
+    }
+}
+
+/// Greets with any type implementing `Greet`.
+///
+/// This is STATIC DISPATCH: the compiler generates a version
+/// of `greet` for each type it is called with.
+pub fn greet<T: Greet>(t: &T) -> String {
+    t.greet()
+}
+
+/// Greets with every item of a collection mixing different types.
+///
+/// A slice can only hold values of a single type, so we use TRAIT
+/// OBJECTS (`&dyn Greet`). This is DYNAMIC DISPATCH: the method to
+/// call is looked up at runtime.
+pub fn greet_all(items: &[&dyn Greet]) -> Vec<String> {
+    items.iter().map(|item| item.greet()).collect()
+}
+
+#[test]
+fn static_and_dynamic_dispatch() {
+    assert_eq!(greet(&Human), "Hi!");
+    assert_eq!(greet(&Cat), "Meow!");
+
+    let items: [&dyn Greet; 3] = [&Human, &Cat, &Human];
+    assert_eq!(greet_all(&items), ["Hi!", "Meow!", "Hi!"]);
+    assert!(greet_all(&[]).is_empty());
+}
+
+/// A registry of greeters looked up by name.
+///
+/// The greeters are of different types so, as with `greet_all`,
+/// we store TRAIT OBJECTS. A `HashMap` owns its values, so rather
+/// than references we store boxed trait objects (`Box<dyn Greet>`).
+#[derive(Default)]
+pub struct GreeterRegistry {
+    greeters: std::collections::HashMap<String, Box<dyn Greet>>,
+}
+
+impl GreeterRegistry {
+    /// Registers `greeter` under `name`, replacing any
+    /// greeter previously registered under that name.
+    pub fn register(&mut self, name: &str, greeter: Box<dyn Greet>) {
+        self.greeters.insert(String::from(name), greeter);
+    }
+
+    /// Greets with the greeter registered under `name`, if any.
+    pub fn greet(&self, name: &str) -> Option<String> {
+        self.greeters.get(name).map(|greeter| greeter.greet())
+    }
+}
+
+#[test]
+fn greeter_registry() {
+    let mut registry = GreeterRegistry::default();
+    registry.register("alice", Box::new(Human));
+    registry.register("felix", Box::new(Cat));
+
+    assert_eq!(registry.greet("alice").as_deref(), Some("Hi!"));
+    assert_eq!(registry.greet("felix").as_deref(), Some("Meow!"));
+    assert_eq!(registry.greet("bob"), None);
+
+    registry.register("alice", Box::new(Cat));
+    assert_eq!(registry.greet("alice").as_deref(), Some("Meow!"));
+}
+
 /// # What `#[derive(TraitName)]` Does
 ///
 /// The `derive` macro **automatically implements common traits** for your type.
@@ -179,6 +269,50 @@ fn derive_macro() {
     // it is OFTEN required that all members implement this trait
 }
 
+/// A `Person` deriving all the traits needed to be cloned,
+/// compared and sorted.
+///
+/// Derived `PartialOrd` and `Ord` compare the fields in the order
+/// they are declared, so persons are ordered by age then by name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Person {
+    pub age: u8,
+    pub name: String,
+}
+
+impl Greet for Person {
+    fn greet(&self) -> String {
+        format!("Hi, I'm {}!", self.name)
+    }
+}
+
+#[test]
+fn derive_person() {
+    let bob = Person {
+        age: 42,
+        name: String::from("bob"),
+    };
+    let bob2 = bob.clone();
+    assert_eq!(bob, bob2);
+
+    let mut people = vec![
+        bob,
+        Person {
+            age: 7,
+            name: String::from("zoe"),
+        },
+    ];
+    people.push(Person {
+        age: 42,
+        name: String::from("alice"),
+    });
+    people.sort();
+    let names: Vec<&str> = people.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["zoe", "alice", "bob"]);
+
+    assert_eq!(greet(&bob2), "Hi, I'm bob!");
+}
+
 /// # From and Into Traits
 ///
 /// These traits provide **type conversion** functionality in Rust.
@@ -252,6 +386,192 @@ fn from_and_into() {
     // println!("my_u8:{:?}", my_u8);
 
     // EXERCISE: implement a way to convert a u8 into a MyU8
+    impl From<u8> for MyU8 {
+        fn from(value: u8) -> Self {
+            Self(value)
+        }
+    }
+
+    assert_eq!(MyU8::from(200u8).0, 200);
+    // implementing From gives us Into for free
+    let my_u8: MyU8 = 200u8.into();
+    assert_eq!(my_u8.0, 200);
 
     // EXERCISE: implement a way to convert a MyU64 int a MyU8
+    impl From<MyU64> for MyU8 {
+        // !!! a u64 does not always fit in a u8 !!!
+        // `as` truncates the value, only its lowest 8 bits are
+        // kept: MyU64(300) becomes MyU8(44) as 300 = 256 + 44.
+        // See TryFrom for a conversion which does not lose data.
+        fn from(value: MyU64) -> Self {
+            Self(value.0 as u8)
+        }
+    }
+
+    assert_eq!(MyU8::from(MyU64(42)).0, 42);
+    assert_eq!(MyU8::from(MyU64(300)).0, 44);
+    assert_eq!(MyU8::from(MyU64(u64::MAX)).0, u8::MAX);
+}
+
+/// # TryFrom and TryInto Traits
+///
+/// They are the **fallible** versions of `From` and `Into`:
+/// ```rust
+/// pub trait TryFrom<T> {
+///     type Error;
+///     fn try_from(value: T) -> Result<Self, Self::Error>;
+/// }
+/// ```
+/// - **Purpose**: convert a value which may not fit in the target type
+/// - **Automatically implemented**: as with `From`, implementing
+///   `TryFrom<T> for U` gives you `TryInto<U> for T` for free
+///
+/// When a conversion can lose data (like a `u64` into a `u8`)
+/// `TryFrom` is the right choice: the caller decides what to do
+/// with the error instead of getting a silently truncated value.
+#[test]
+fn try_from_and_try_into() {
+    #[derive(Debug, PartialEq)]
+    struct MyU8(u8);
+
+    #[derive(Debug)]
+    struct MyU64(u64);
+
+    // the error returned when the value does not fit, it carries
+    // the value so that the caller knows what went wrong
+    #[derive(Debug, PartialEq)]
+    struct OverflowError(u64);
+
+    impl TryFrom<MyU64> for MyU8 {
+        type Error = OverflowError;
+
+        fn try_from(value: MyU64) -> Result<Self, Self::Error> {
+            if value.0 > u8::MAX as u64 {
+                return Err(OverflowError(value.0));
+            }
+            Ok(Self(value.0 as u8))
+        }
+    }
+
+    assert_eq!(MyU8::try_from(MyU64(42)), Ok(MyU8(42)));
+    assert_eq!(MyU8::try_from(MyU64(255)), Ok(MyU8(255)));
+    assert_eq!(MyU8::try_from(MyU64(300)), Err(OverflowError(300)));
+
+    // we get try_into for free
+    let res: Result<MyU8, _> = MyU64(300).try_into();
+    println!("MyU64(300).try_into()={res:?}");
+    assert!(res.is_err());
+}
+
+/// Default methods can do a lot with a single required method.
+///
+/// Here implementors only say how to get their items as a slice
+/// and they get `mean`, `min` and `max` for free. Statistics make
+/// no sense without items so they all return `None` when empty.
+pub trait VecStats {
+    // the ASSOCIATED TYPE of the items, each implementor chooses it
+    type Item: Copy + PartialOrd + Into<f64>;
+
+    fn items(&self) -> &[Self::Item];
+
+    fn mean(&self) -> Option<f64> {
+        let items = self.items();
+        if items.is_empty() {
+            return None;
+        }
+        let sum: f64 = items.iter().map(|i| (*i).into()).sum();
+        Some(sum / items.len() as f64)
+    }
+
+    fn min(&self) -> Option<Self::Item> {
+        self.items()
+            .iter()
+            .copied()
+            .reduce(|min, i| if i < min { i } else { min })
+    }
+
+    fn max(&self) -> Option<Self::Item> {
+        self.items()
+            .iter()
+            .copied()
+            .reduce(|max, i| if i > max { i } else { max })
+    }
+}
+
+// we can implement our own traits on standard types
+impl VecStats for [i32] {
+    type Item = i32;
+
+    fn items(&self) -> &[i32] {
+        self
+    }
+}
+
+impl VecStats for [f64] {
+    type Item = f64;
+
+    fn items(&self) -> &[f64] {
+        self
+    }
+}
+
+#[test]
+fn trait_on_std_types() {
+    let ints: &[i32] = &[3, -1, 4, 2];
+    assert_eq!(ints.mean(), Some(2.0));
+    // [i32] implements Ord which also has min and max methods, so
+    // we must tell the compiler which trait we want to call
+    assert_eq!(VecStats::min(ints), Some(-1));
+    assert_eq!(VecStats::max(ints), Some(4));
+
+    let floats: &[f64] = &[1.5, 0.5, 2.5];
+    assert_eq!(floats.mean(), Some(1.5));
+    assert_eq!(floats.min(), Some(0.5));
+    assert_eq!(floats.max(), Some(2.5));
+
+    let empty: &[i32] = &[];
+    assert_eq!(empty.mean(), None);
+    assert_eq!(VecStats::min(empty), None);
+    assert_eq!(VecStats::max(empty), None);
+
+    let empty: &[f64] = &[];
+    assert_eq!(empty.mean(), None);
+    assert_eq!(empty.min(), None);
+    assert_eq!(empty.max(), None);
+}
+
+/// # Blanket Implementations
+///
+/// A trait can be implemented for every type satisfying some
+/// bounds at once: this is a BLANKET IMPLEMENTATION.
+///
+/// The standard library uses it a lot, this is how implementing
+/// `From` gives you `Into` for free or how any `Display` type gets
+/// a `to_string` method.
+pub trait Describe {
+    fn describe(&self) -> String;
+}
+
+// every type implementing Debug, even the ones we don't know
+// about yet, now implements Describe
+impl<T: std::fmt::Debug> Describe for T {
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+#[test]
+fn blanket_implementation() {
+    assert_eq!(42.describe(), "42");
+    assert_eq!(vec![1, 2, 3].describe(), "[1, 2, 3]");
+    assert_eq!("hello".describe(), "\"hello\"");
+
+    let p = Person {
+        age: 42,
+        name: String::from("bob"),
+    };
+    assert_eq!(p.describe(), "Person { age: 42, name: \"bob\" }");
+
+    // EXERCISE: uncomment below and understand the error
+    // Human.describe();
 }